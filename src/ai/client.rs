@@ -1,11 +1,21 @@
 use std::collections::HashSet;
+use std::fs;
 use std::time::Duration;
 
-use anyhow::{bail, Context, Result};
-use reqwest::Client;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Certificate, Client, NoProxy, Proxy};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-use crate::app::Config;
+use crate::ai::error::AiError;
+use crate::ai::structured::{
+    append_citations, json_schema, merge_sources, SCHEMA_NAME, STRUCTURED_INSTRUCTION,
+};
+use crate::app::{expand_home, Config};
+
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+const MAX_BACKOFF_DELAY: Duration = Duration::from_secs(30);
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
 
 #[derive(Debug, Serialize)]
 struct ChatRequest {
@@ -13,6 +23,8 @@ struct ChatRequest {
     messages: Vec<Message>,
     max_tokens: u32,
     temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -24,6 +36,7 @@ struct Message {
 #[derive(Debug, Deserialize)]
 struct ChatResponse {
     choices: Vec<Choice>,
+    usage: Option<TokenUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -44,6 +57,8 @@ struct ResponsesRequest {
     tools: Vec<ResponseTool>,
     tool_choice: String,
     max_output_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -56,6 +71,7 @@ struct ResponsesResponse {
     output_text: Option<String>,
     #[serde(default)]
     output: Vec<ResponseOutputItem>,
+    usage: Option<TokenUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -77,36 +93,52 @@ struct ResponseAnnotation {
     title: Option<String>,
 }
 
+/// Chat Completions 使用 `prompt_tokens`/`completion_tokens`，Responses 使用 `input_tokens`/`output_tokens`。
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct TokenUsage {
+    #[serde(default, alias = "input_tokens")]
+    pub prompt_tokens: u64,
+    #[serde(default, alias = "output_tokens")]
+    pub completion_tokens: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct AiAnswer {
+    pub text: String,
+    pub usage: Option<TokenUsage>,
+}
+
 pub struct AiClient {
     client: Client,
     config: Config,
+    api_key: String,
 }
 
 impl AiClient {
-    pub fn new(config: Config) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(120))
-            .build()
-            .expect("创建 HTTP 客户端失败");
-        Self { client, config }
+    pub fn new(config: Config) -> Result<Self, AiError> {
+        let api_key = config
+            .resolve_api_key()
+            .map_err(|err| AiError::Config(format!("{err:#}")))?;
+        let client = build_http_client(&config).map_err(AiError::Config)?;
+        Ok(Self {
+            client,
+            config,
+            api_key,
+        })
     }
 
-    pub async fn query_with_context(&self, word: &str, context: &str) -> Result<String> {
+    pub async fn query(&self, user_prompt: &str) -> Result<AiAnswer, AiError> {
         if self.config.enable_web_search {
-            self.query_with_web_search(word, context).await
+            self.query_with_web_search(user_prompt).await
         } else {
-            self.query_chat_completions(word, context).await
+            self.query_chat_completions(user_prompt).await
         }
     }
 
-    async fn query_chat_completions(&self, word: &str, context: &str) -> Result<String> {
+    async fn query_chat_completions(&self, user_prompt: &str) -> Result<AiAnswer, AiError> {
         let url = self.chat_completions_url();
-        let system = self.config.system_prompt.clone();
-        let user = crate::ai::prompt::build_user_prompt_with_context(
-            word,
-            &self.config.answer_language,
-            context,
-        );
+        let system = self.system_prompt();
+        let user = user_prompt.to_string();
 
         let request = ChatRequest {
             model: self.config.model.clone(),
@@ -122,73 +154,131 @@ impl AiClient {
             ],
             max_tokens: 4096,
             temperature: 0.7,
+            response_format: self.config.structured_answers.then(|| {
+                serde_json::json!({
+                    "type": "json_schema",
+                    "json_schema": {
+                        "name": SCHEMA_NAME,
+                        "strict": true,
+                        "schema": json_schema(),
+                    }
+                })
+            }),
         };
 
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await
-            .with_context(|| format!("AI 请求失败: {url}"))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            bail!("AI API 返回错误 ({status}): {body}");
-        }
-
-        let chat_response: ChatResponse = response.json().await.context("解析 AI 响应失败")?;
+        let chat_response: ChatResponse = self.post_with_retry(&url, &request).await?;
         let content = chat_response
             .choices
             .first()
-            .context("AI 响应为空")?
+            .ok_or_else(|| AiError::BadResponse("AI 响应为空".to_string()))?
             .message
             .content
             .clone();
 
-        Ok(content)
+        Ok(AiAnswer {
+            text: content,
+            usage: chat_response.usage,
+        })
     }
 
-    async fn query_with_web_search(&self, word: &str, context: &str) -> Result<String> {
+    async fn query_with_web_search(&self, user_prompt: &str) -> Result<AiAnswer, AiError> {
         let url = self.responses_url();
         let request = ResponsesRequest {
             model: self.config.model.clone(),
-            instructions: self.config.system_prompt.clone(),
-            input: crate::ai::prompt::build_user_prompt_with_context(
-                word,
-                &self.config.answer_language,
-                context,
-            ),
+            instructions: self.system_prompt(),
+            input: user_prompt.to_string(),
             tools: vec![ResponseTool {
                 r#type: "web_search",
             }],
             tool_choice: "auto".to_string(),
             max_output_tokens: 4096,
+            text: self.config.structured_answers.then(|| {
+                serde_json::json!({
+                    "format": {
+                        "type": "json_schema",
+                        "name": SCHEMA_NAME,
+                        "strict": true,
+                        "schema": json_schema(),
+                    }
+                })
+            }),
+        };
+
+        let responses: ResponsesResponse = self.post_with_retry(&url, &request).await?;
+        let text = extract_response_text(&responses)
+            .ok_or_else(|| AiError::BadResponse("AI 网页搜索响应为空".to_string()))?;
+        let citations = collect_citations(&responses);
+        // 结构化回答必须保持为 JSON，引用并入 `sources` 字段而不是追加在末尾
+        let text = if self.config.structured_answers {
+            merge_sources(text, &citations)
+        } else {
+            append_citations(text, &citations)
         };
+        Ok(AiAnswer {
+            text,
+            usage: responses.usage,
+        })
+    }
+
+    async fn post_with_retry<T, R>(&self, url: &str, body: &T) -> Result<R, AiError>
+    where
+        T: Serialize,
+        R: DeserializeOwned,
+    {
+        let mut attempt = 0;
+        loop {
+            let err = match self.post_once(url, body).await {
+                Ok(response) => return Ok(response),
+                Err(err) => err,
+            };
+
+            if attempt >= self.config.max_retries || !err.is_retryable() {
+                return Err(err.with_retries(attempt));
+            }
+            let Some(delay) = retry_delay(&err, attempt) else {
+                return Err(err.with_retries(attempt));
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
 
+    async fn post_once<T, R>(&self, url: &str, body: &T) -> Result<R, AiError>
+    where
+        T: Serialize,
+        R: DeserializeOwned,
+    {
         let response = self
             .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .post(url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
-            .json(&request)
+            .json(body)
             .send()
             .await
-            .with_context(|| format!("AI 网页搜索请求失败: {url}"))?;
+            .map_err(AiError::from_reqwest)?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            bail!("AI 网页搜索返回错误 ({status}): {body}");
+        let status = response.status();
+        if !status.is_success() {
+            let headers = response.headers().clone();
+            let text = response.text().await.unwrap_or_default();
+            return Err(AiError::from_status(status, &headers, &text));
         }
 
-        let responses: ResponsesResponse =
-            response.json().await.context("解析 AI 网页搜索响应失败")?;
-        let text = extract_response_text(&responses).context("AI 网页搜索响应为空")?;
-        Ok(append_citations(text, &responses))
+        let text = response.text().await.map_err(AiError::from_reqwest)?;
+        serde_json::from_str(&text).map_err(|err| AiError::BadResponse(err.to_string()))
+    }
+
+    fn system_prompt(&self) -> String {
+        if self.config.structured_answers {
+            format!(
+                "{}\n\n{}",
+                self.config.system_prompt, STRUCTURED_INSTRUCTION
+            )
+        } else {
+            self.config.system_prompt.clone()
+        }
     }
 
     fn api_base_url(&self) -> String {
@@ -219,6 +309,62 @@ impl AiClient {
     }
 }
 
+/// 按配置组装 HTTP 客户端：超时、代理、额外 CA 证书和自定义请求头。
+fn build_http_client(config: &Config) -> Result<Client, String> {
+    let mut builder = Client::builder()
+        .timeout(Duration::from_secs(config.request_timeout_secs.max(1)))
+        .connect_timeout(Duration::from_secs(config.connect_timeout_secs.max(1)));
+
+    let proxy_url = config.proxy.trim();
+    if !proxy_url.is_empty() {
+        let proxy = Proxy::all(proxy_url)
+            .map_err(|err| format!("代理地址无效: {proxy_url}: {err}"))?
+            .no_proxy(NoProxy::from_string(config.no_proxy.trim()));
+        builder = builder.proxy(proxy);
+    }
+
+    let ca_path = config.ca_cert_path.trim();
+    if !ca_path.is_empty() {
+        let path = expand_home(ca_path);
+        let pem = fs::read(&path)
+            .map_err(|err| format!("读取 CA 证书失败: {}: {err}", path.display()))?;
+        let certs = Certificate::from_pem_bundle(&pem)
+            .map_err(|err| format!("解析 CA 证书失败: {}: {err}", path.display()))?;
+        if certs.is_empty() {
+            return Err(format!("CA 证书文件中没有证书: {}", path.display()));
+        }
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+
+    let mut headers = HeaderMap::new();
+    for (name, value) in &config.custom_headers {
+        let header_name = HeaderName::from_bytes(name.trim().as_bytes())
+            .map_err(|_| format!("请求头名称无效: {name}"))?;
+        let header_value =
+            HeaderValue::from_str(value.trim()).map_err(|_| format!("请求头 {name} 的值无效"))?;
+        headers.insert(header_name, header_value);
+    }
+    builder
+        .default_headers(headers)
+        .build()
+        .map_err(|err| format!("创建 HTTP 客户端失败: {err}"))
+}
+
+/// 优先使用服务器给出的 `Retry-After`；等待时间过长时不再重试，直接把限流信息交给用户。
+fn retry_delay(err: &AiError, attempt: u32) -> Option<Duration> {
+    match err.retry_after() {
+        Some(delay) if delay > MAX_RETRY_AFTER => None,
+        Some(delay) => Some(delay),
+        None => Some(
+            RETRY_BASE_DELAY
+                .saturating_mul(2u32.saturating_pow(attempt))
+                .min(MAX_BACKOFF_DELAY),
+        ),
+    }
+}
+
 fn extract_response_text(response: &ResponsesResponse) -> Option<String> {
     if let Some(text) = response
         .output_text
@@ -239,7 +385,8 @@ fn extract_response_text(response: &ResponsesResponse) -> Option<String> {
     (!text.trim().is_empty()).then_some(text)
 }
 
-fn append_citations(mut text: String, response: &ResponsesResponse) -> String {
+/// 网页搜索返回的引用，格式为 `标题: 网址`，按网址去重。
+fn collect_citations(response: &ResponsesResponse) -> Vec<String> {
     let mut seen = HashSet::new();
     let mut citations = Vec::new();
 
@@ -259,11 +406,5 @@ fn append_citations(mut text: String, response: &ResponsesResponse) -> String {
         let title = annotation.title.as_deref().unwrap_or(url);
         citations.push(format!("{}: {}", title, url));
     }
-
-    if !citations.is_empty() {
-        text.push_str("\n\n来源:\n");
-        text.push_str(&citations.join("\n"));
-    }
-
-    text
+    citations
 }
@@ -0,0 +1,177 @@
+use std::fmt;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+
+const MAX_DETAIL_CHARS: usize = 300;
+
+#[derive(Debug)]
+pub enum AiError {
+    Auth {
+        status: StatusCode,
+        detail: String,
+    },
+    RateLimit {
+        retry_after: Option<Duration>,
+        detail: String,
+    },
+    Server {
+        status: StatusCode,
+        detail: String,
+        /// 已自动重试的次数
+        retries: u32,
+    },
+    Rejected {
+        status: StatusCode,
+        detail: String,
+    },
+    Timeout,
+    Network(String),
+    BadResponse(String),
+    Config(String),
+}
+
+impl AiError {
+    pub fn from_status(status: StatusCode, headers: &HeaderMap, body: &str) -> Self {
+        let detail = extract_error_message(body);
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Auth { status, detail },
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimit {
+                retry_after: parse_retry_after(headers),
+                detail,
+            },
+            StatusCode::REQUEST_TIMEOUT | StatusCode::GATEWAY_TIMEOUT => Self::Timeout,
+            status if status.is_server_error() => Self::Server {
+                status,
+                detail,
+                retries: 0,
+            },
+            status => Self::Rejected { status, detail },
+        }
+    }
+
+    pub fn from_reqwest(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            Self::Timeout
+        } else if err.is_decode() {
+            Self::BadResponse(err.to_string())
+        } else {
+            Self::Network(err.to_string())
+        }
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::RateLimit { .. } | Self::Server { .. } | Self::Timeout | Self::Network(_)
+        )
+    }
+
+    /// 记下放弃前已重试的次数，用于提示。
+    pub fn with_retries(mut self, count: u32) -> Self {
+        if let Self::Server { retries, .. } = &mut self {
+            *retries = count;
+        }
+        self
+    }
+
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimit { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    fn friendly_message(&self) -> String {
+        match self {
+            Self::Auth { status, .. } => {
+                format!(
+                    "API Key 无效或没有访问权限 ({status})，请在配置页检查 API Key 和 API 地址。"
+                )
+            }
+            Self::RateLimit {
+                retry_after: Some(delay),
+                ..
+            } => format!(
+                "请求过于频繁，已被限流，请约 {} 秒后再试。",
+                delay.as_secs().max(1)
+            ),
+            Self::RateLimit { .. } => "请求过于频繁或额度不足，已被限流，请稍后再试。".to_string(),
+            Self::Server {
+                status, retries: 0, ..
+            } => format!("AI 服务暂时不可用 ({status})，请稍后再试。"),
+            Self::Server {
+                status, retries, ..
+            } => {
+                format!("AI 服务暂时不可用 ({status})，已自动重试 {retries} 次仍失败，请稍后再试。")
+            }
+            Self::Rejected { status, .. } => {
+                format!("AI 服务拒绝了请求 ({status})，请检查模型名称和配置。")
+            }
+            Self::Timeout => "AI 请求超时，可在配置页调大请求超时时间。".to_string(),
+            Self::Network(_) => "无法连接 AI 服务，请检查网络和 API 地址。".to_string(),
+            Self::BadResponse(_) => "AI 服务返回了无法识别的响应。".to_string(),
+            Self::Config(_) => "AI 配置有误，请在配置页检查 API Key 来源等设置。".to_string(),
+        }
+    }
+
+    fn detail(&self) -> Option<&str> {
+        match self {
+            Self::Auth { detail, .. }
+            | Self::RateLimit { detail, .. }
+            | Self::Server { detail, .. }
+            | Self::Rejected { detail, .. }
+            | Self::Network(detail)
+            | Self::BadResponse(detail)
+            | Self::Config(detail) => Some(detail.as_str()),
+            Self::Timeout => None,
+        }
+        .filter(|detail| !detail.trim().is_empty())
+    }
+}
+
+impl fmt::Display for AiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.friendly_message())?;
+        if let Some(detail) = self.detail() {
+            write!(f, "\n\n详细信息: {detail}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for AiError {}
+
+/// 兼容 `Retry-After` 的两种写法：秒数或 HTTP 日期。
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let secs = (date.timestamp() - chrono::Utc::now().timestamp()).max(0);
+    Some(Duration::from_secs(secs as u64))
+}
+
+fn extract_error_message(body: &str) -> String {
+    let message = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|value| {
+            value
+                .pointer("/error/message")
+                .or_else(|| value.get("message"))
+                .and_then(|message| message.as_str())
+                .map(ToString::to_string)
+        })
+        .unwrap_or_else(|| body.trim().to_string());
+
+    if message.chars().count() > MAX_DETAIL_CHARS {
+        let mut truncated: String = message.chars().take(MAX_DETAIL_CHARS).collect();
+        truncated.push('…');
+        truncated
+    } else {
+        message
+    }
+}
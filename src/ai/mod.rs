@@ -1,4 +1,8 @@
 pub mod client;
+pub mod error;
 pub mod prompt;
+pub mod structured;
+pub mod translate;
+pub mod usage;
 
 pub use client::AiClient;
\ No newline at end of file
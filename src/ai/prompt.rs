@@ -1,5 +1,21 @@
+use serde::{Deserialize, Serialize};
+
 const DEFAULT_SYSTEM_PROMPT: &str = r#"你是一位专业的英语学习助手，面向中文用户解释英文单词、短语和新兴表达。
 
+输出会在终端 TUI 中按 Markdown 渲染：
+可以使用标题、加粗、斜体、列表、引用、行内代码和链接。
+避免使用复杂表格、HTML 和脚注。
+使用短段落，每段用空行分隔。
+需要分节时使用二级标题，例如“## 核心含义”“## 真实例句”“## 最新用法”“## 来源”。
+
+回答时请优先关注基本含义、词性、发音、中文解释、常见搭配、语域差异和近义词辨析。
+当问题涉及新词、流行语、网络表达或近期用法时，结合最新网页信息说明真实使用场景。
+如果使用了网页搜索，请给出来源名称或链接，避免编造引用。
+中文解释为主，英文例句附中文翻译。"#;
+
+/// 回答改为按 Markdown 渲染之前的默认系统提示词，要求模型不要使用 Markdown。
+const LEGACY_SYSTEM_PROMPT: &str = r#"你是一位专业的英语学习助手，面向中文用户解释英文单词、短语和新兴表达。
+
 输出必须适合终端 TUI 阅读：
 不要使用 Markdown。
 不要使用 #、**、*、-、>、`、表格、代码块、Markdown 链接或脚注。
@@ -13,39 +29,313 @@ const DEFAULT_SYSTEM_PROMPT: &str = r#"你是一位专业的英语学习助手
 如果使用了网页搜索，请给出来源名称或链接，避免编造引用。
 中文解释为主，英文例句附中文翻译。"#;
 
-pub fn build_user_prompt_with_context(word: &str, answer_lang: &str, context: &str) -> String {
-    let context_section = if context.trim().is_empty() {
-        String::new()
-    } else {
-        format!("参考词典内容：\n{}\n\n", context)
-    };
+pub const TEMPLATE_PLACEHOLDERS: &[&str] = &["word", "language", "context", "definition", "source"];
+
+const TEMPLATE_HEADER_PREFIX: &str = "=== ";
+const TEMPLATE_HEADER_SUFFIX: &str = " ===";
 
-    format!(
-        r#"请查询英文单词、短语或表达“{}”，并用{}回答。
+const DEFINE_TEMPLATE: &str = r#"请查询英文单词、短语或表达“{word}”，并用{language}回答。
 
-{}请按纯文本格式提供以下内容，不要使用 Markdown：
+参考词典内容：{context}
 
-核心含义
+请按以下小节提供内容：
+
+## 核心含义
 说明主要意思、词性、发音和中文解释。
 
-可靠释义
+## 可靠释义
 概括权威词典或可靠资料中的释义。
 
-真实例句
+## 真实例句
 提供真实语境例句，附中文翻译和使用场景。
 
-最新用法
+## 最新用法
 如果这是新词、网络流行语或近期用法，请说明最新使用趋势。
 
-辨析与搭配
+## 辨析与搭配
 说明近义词差异、常见搭配和文化注意事项。
 
-来源
-如果使用了网页搜索，请列出普通文本来源名称或 URL。"#,
-        word, answer_lang, context_section
-    )
+## 来源
+如果使用了网页搜索，请列出来源名称或链接。"#;
+
+const ETYMOLOGY_TEMPLATE: &str = r#"请用{language}讲解英文单词“{word}”的词源。
+
+参考词典内容（来自 {source}）：
+{definition}
+
+请说明词根词缀、最早来源语言、词义的历史演变，以及同源词。"#;
+
+const COLLOCATIONS_TEMPLATE: &str = r#"请用{language}列出英文单词或短语“{word}”的常见搭配。
+
+参考词典内容：{context}
+
+按动词、形容词、介词等搭配类型分组，每个搭配给出一个英文例句和翻译，并指出容易用错的搭配。"#;
+
+const JAPANESE_TEMPLATE: &str = r#"请把英文单词或短语“{word}”翻译成日语。
+
+参考词典内容（来自 {source}）：
+{definition}
+
+给出常用的日语对应词、读音（假名）、用法区别，以及附日语翻译的英文例句。"#;
+
+/// 句子翻译模式使用的模板名称；`{word}` 为整句，`{definition}` 为词典中查到的难词释义。
+pub const TRANSLATION_TEMPLATE_NAME: &str = "句子翻译";
+
+const TRANSLATION_TEMPLATE: &str = r#"请把下面的英文句子翻译成{language}，并讲解其中的语法。
+
+句子：
+{word}
+
+词典中查到的难词释义：
+{definition}
+
+请按以下小节回答：
+
+## 译文
+给出准确、通顺的译文。
+
+## 语法分析
+拆解句子主干、从句和特殊结构，说明时态、语态等关键语法点。
+
+## 难词
+结合上面的词典释义，逐个说明难词在本句中的具体含义；词典中没有收录的难词也请补充。"#;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub name: String,
+    pub template: String,
+}
+
+impl PromptTemplate {
+    fn new(name: &str, template: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            template: template.to_string(),
+        }
+    }
+}
+
+pub struct PromptVars<'a> {
+    pub word: &'a str,
+    pub language: &'a str,
+    pub context: &'a str,
+    pub definition: &'a str,
+    pub source: &'a str,
+}
+
+impl PromptVars<'_> {
+    fn get(&self, name: &str) -> Option<&str> {
+        match name {
+            "word" => Some(self.word),
+            "language" => Some(self.language),
+            "context" => Some(self.context),
+            "definition" => Some(self.definition),
+            "source" => Some(self.source),
+            _ => None,
+        }
+    }
+}
+
+pub fn default_prompt_templates() -> Vec<PromptTemplate> {
+    vec![
+        PromptTemplate::new("释义", DEFINE_TEMPLATE),
+        PromptTemplate::new("词源", ETYMOLOGY_TEMPLATE),
+        PromptTemplate::new("搭配", COLLOCATIONS_TEMPLATE),
+        PromptTemplate::new("日语翻译", JAPANESE_TEMPLATE),
+        default_translation_template(),
+    ]
+}
+
+pub fn default_translation_template() -> PromptTemplate {
+    PromptTemplate::new(TRANSLATION_TEMPLATE_NAME, TRANSLATION_TEMPLATE)
+}
+
+/// 替换模板中的 `{name}` 占位符；`{{` 和 `}}` 输出字面量花括号，未知占位符原样保留。
+/// 占位符全部为空的行 (如没有选中词条时的“参考词典内容：{context}”) 整行省略，
+/// 省略后相邻的空行合并为一行。
+pub fn render_template(template: &str, vars: &PromptVars) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut after_dropped = false;
+    for line in template.split_inclusive('\n') {
+        let (rendered, placeholders, filled) = render_line(line, vars);
+        if placeholders > 0 && !filled {
+            after_dropped = true;
+            continue;
+        }
+        let blank = line.trim().is_empty();
+        if after_dropped && blank && (output.is_empty() || output.ends_with("\n\n")) {
+            continue;
+        }
+        if !blank {
+            after_dropped = false;
+        }
+        output.push_str(&rendered);
+    }
+    output
+}
+
+/// 渲染一行，同时返回其中已知占位符的个数，以及是否有占位符替换成了非空内容。
+fn render_line(line: &str, vars: &PromptVars) -> (String, usize, bool) {
+    let mut output = String::with_capacity(line.len());
+    let mut placeholders = 0;
+    let mut filled = false;
+    let mut rest = line;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        output.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            output.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+
+        match placeholder_at(rest).and_then(|(name, len)| vars.get(name).map(|v| (v, len))) {
+            Some((value, len)) => {
+                output.push_str(value);
+                placeholders += 1;
+                filled |= !value.trim().is_empty();
+                rest = &rest[len..];
+            }
+            None => {
+                output.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+    (output, placeholders, filled)
+}
+
+pub fn unknown_placeholders(template: &str) -> Vec<String> {
+    let mut unknown = Vec::new();
+    let mut rest = template;
+
+    while let Some(pos) = rest.find('{') {
+        rest = &rest[pos..];
+        if rest.starts_with("{{") {
+            rest = &rest[2..];
+            continue;
+        }
+
+        match placeholder_at(rest) {
+            Some((name, len)) => {
+                if !TEMPLATE_PLACEHOLDERS.contains(&name) && !unknown.iter().any(|u| u == name) {
+                    unknown.push(name.to_string());
+                }
+                rest = &rest[len..];
+            }
+            None => rest = &rest[1..],
+        }
+    }
+
+    unknown
+}
+
+/// 把模板列表写成可编辑文本，每个模板以 `=== 名称 ===` 行开头。
+pub fn templates_to_text(templates: &[PromptTemplate]) -> String {
+    templates
+        .iter()
+        .map(|template| {
+            format!(
+                "{}{}{}\n{}",
+                TEMPLATE_HEADER_PREFIX,
+                template.name,
+                TEMPLATE_HEADER_SUFFIX,
+                template.template.trim_end()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+pub fn parse_templates_text(text: &str) -> Result<Vec<PromptTemplate>, String> {
+    let mut templates: Vec<PromptTemplate> = Vec::new();
+
+    for line in text.lines() {
+        if let Some(name) = template_header_name(line) {
+            if name.is_empty() {
+                return Err("模板名称不能为空".to_string());
+            }
+            if templates.iter().any(|template| template.name == name) {
+                return Err(format!("模板名称重复: {name}"));
+            }
+            templates.push(PromptTemplate::new(name, ""));
+            continue;
+        }
+
+        let Some(current) = templates.last_mut() else {
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Err(format!(
+                "模板内容前缺少 {}名称{} 标题行",
+                TEMPLATE_HEADER_PREFIX, TEMPLATE_HEADER_SUFFIX
+            ));
+        };
+        current.template.push_str(line);
+        current.template.push('\n');
+    }
+
+    if templates.is_empty() {
+        return Err("至少需要一个模板".to_string());
+    }
+
+    for template in &mut templates {
+        template.template = template.template.trim().to_string();
+        if template.template.is_empty() {
+            return Err(format!("模板 {} 内容为空", template.name));
+        }
+        let unknown = unknown_placeholders(&template.template);
+        if !unknown.is_empty() {
+            return Err(format!(
+                "模板 {} 含未知占位符: {}（可用: {}）",
+                template.name,
+                unknown
+                    .iter()
+                    .map(|name| format!("{{{name}}}"))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                TEMPLATE_PLACEHOLDERS
+                    .iter()
+                    .map(|name| format!("{{{name}}}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ));
+        }
+    }
+
+    Ok(templates)
+}
+
+fn template_header_name(line: &str) -> Option<&str> {
+    line.trim()
+        .strip_prefix(TEMPLATE_HEADER_PREFIX)?
+        .strip_suffix(TEMPLATE_HEADER_SUFFIX)
+        .map(str::trim)
+}
+
+/// 识别以 `{` 开头的 `{name}`，返回名称和占用的字节数。
+fn placeholder_at(text: &str) -> Option<(&str, usize)> {
+    if !text.starts_with('{') {
+        return None;
+    }
+    let end = text.find('}')?;
+    let name = &text[1..end];
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+    valid.then_some((name, end + 1))
 }
 
 pub fn default_system_prompt() -> &'static str {
     DEFAULT_SYSTEM_PROMPT
 }
+
+/// 配置中的系统提示词是否仍是未经修改的旧版默认值。
+pub fn is_legacy_system_prompt(prompt: &str) -> bool {
+    prompt.trim() == LEGACY_SYSTEM_PROMPT
+}
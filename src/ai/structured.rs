@@ -0,0 +1,167 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+pub const SCHEMA_NAME: &str = "dictionary_answer";
+
+pub const STRUCTURED_INSTRUCTION: &str =
+    "请只输出符合给定 JSON Schema 的 JSON 对象，不要输出其他文字。";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StructuredAnswer {
+    pub core_meaning: String,
+    #[serde(default)]
+    pub senses: Vec<Sense>,
+    #[serde(default)]
+    pub examples: Vec<Example>,
+    #[serde(default)]
+    pub collocations: Vec<String>,
+    #[serde(default)]
+    pub sources: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Sense {
+    #[serde(default)]
+    pub part_of_speech: String,
+    pub meaning: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Example {
+    pub sentence: String,
+    #[serde(default)]
+    pub translation: String,
+}
+
+/// 与 `StructuredAnswer` 对应的 JSON Schema，满足 strict 模式要求（所有字段必填、禁止额外字段）。
+pub fn json_schema() -> Value {
+    let string_array = json!({ "type": "array", "items": { "type": "string" } });
+    json!({
+        "type": "object",
+        "properties": {
+            "core_meaning": { "type": "string" },
+            "senses": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "part_of_speech": { "type": "string" },
+                        "meaning": { "type": "string" }
+                    },
+                    "required": ["part_of_speech", "meaning"],
+                    "additionalProperties": false
+                }
+            },
+            "examples": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "sentence": { "type": "string" },
+                        "translation": { "type": "string" }
+                    },
+                    "required": ["sentence", "translation"],
+                    "additionalProperties": false
+                }
+            },
+            "collocations": string_array,
+            "sources": string_array
+        },
+        "required": ["core_meaning", "senses", "examples", "collocations", "sources"],
+        "additionalProperties": false
+    })
+}
+
+/// 解析结构化回答；兼容模型把 JSON 包在 ``` 代码块里的情况。
+pub fn parse_structured_answer(text: &str) -> Option<StructuredAnswer> {
+    serde_json::from_str(json_body(text)).ok()
+}
+
+/// 把网页搜索的引用并入结构化回答的 `sources` 字段，回答仍是可解析的 JSON；
+/// 回答不是带 `sources` 数组的 JSON 对象时改为把引用追加在末尾。
+pub fn merge_sources(text: String, citations: &[String]) -> String {
+    if citations.is_empty() {
+        return text;
+    }
+    let Ok(mut value) = serde_json::from_str::<Value>(json_body(&text)) else {
+        return append_citations(text, citations);
+    };
+    let Some(sources) = value.get_mut("sources").and_then(Value::as_array_mut) else {
+        return append_citations(text, citations);
+    };
+    for citation in citations {
+        if !sources
+            .iter()
+            .any(|source| source.as_str() == Some(citation))
+        {
+            sources.push(Value::String(citation.clone()));
+        }
+    }
+    value.to_string()
+}
+
+/// 非结构化回答的引用以「来源」段落追加在末尾。
+pub fn append_citations(mut text: String, citations: &[String]) -> String {
+    if !citations.is_empty() {
+        text.push_str("\n\n来源:\n");
+        text.push_str(&citations.join("\n"));
+    }
+
+    text
+}
+
+fn json_body(text: &str) -> &str {
+    let trimmed = text.trim();
+    trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.trim_end().strip_suffix("```"))
+        .unwrap_or(trimmed)
+        .trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn citations() -> Vec<String> {
+        vec!["Example: https://example.com".to_string()]
+    }
+
+    #[test]
+    fn merge_sources_adds_citations_to_json() {
+        let text = "```json\n{\"core_meaning\":\"跑\",\"sources\":[\"Example: https://example.com\",\"词典\"]}\n```";
+        let merged = merge_sources(
+            text.to_string(),
+            &["Other: https://other.org".to_string(), "词典".to_string()],
+        );
+        let answer = parse_structured_answer(&merged).unwrap();
+        assert_eq!(
+            answer.sources,
+            [
+                "Example: https://example.com",
+                "词典",
+                "Other: https://other.org"
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_sources_appends_citations_to_plain_text() {
+        let merged = merge_sources("run: 跑".to_string(), &citations());
+        assert_eq!(merged, "run: 跑\n\n来源:\nExample: https://example.com");
+    }
+
+    #[test]
+    fn merge_sources_appends_citations_without_sources_array() {
+        let text = "{\"core_meaning\":\"跑\"}";
+        let merged = merge_sources(text.to_string(), &citations());
+        assert!(merged.starts_with(text));
+        assert!(merged.ends_with("来源:\nExample: https://example.com"));
+    }
+
+    #[test]
+    fn merge_sources_keeps_text_without_citations() {
+        assert_eq!(merge_sources("not json".to_string(), &[]), "not json");
+    }
+}
@@ -0,0 +1,86 @@
+/// 难词之外的常见词，不再回查词典。
+#[rustfmt::skip]
+const COMMON_WORDS: &[&str] = &[
+    "about", "above", "after", "again", "against", "also", "although", "among", "another",
+    "around", "because", "been", "before", "being", "below", "between", "both", "could", "does",
+    "doing", "down", "during", "each", "even", "every", "from", "further", "have", "having",
+    "here", "however", "into", "itself", "just", "like", "made", "make", "many", "more", "most",
+    "much", "must", "never", "only", "other", "others", "over", "same", "should", "since",
+    "some", "such", "than", "that", "their", "them", "themselves", "then", "there", "these",
+    "they", "this", "those", "through", "thus", "under", "until", "upon", "very", "were",
+    "what", "when", "where", "whether", "which", "while", "whose", "will", "with", "within",
+    "without", "would", "your", "yours",
+];
+
+const MIN_DIFFICULT_WORD_CHARS: usize = 4;
+
+/// 多个单词组成的句子进入翻译模式；两三个词的短语仍按词条查询。
+pub fn is_sentence(query: &str) -> bool {
+    let trimmed = query.trim();
+    let word_count = trimmed.split_whitespace().count();
+    word_count >= 4 || (word_count >= 2 && trimmed.ends_with(['.', '?', '!', '。', '？', '！']))
+}
+
+/// 按出现顺序返回句子中值得回查词典的单词 (小写、去重)。
+pub fn difficult_words(sentence: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    for token in sentence.split(|ch: char| !(ch.is_ascii_alphabetic() || ch == '-')) {
+        let word = token.trim_matches('-').to_ascii_lowercase();
+        if word.chars().count() < MIN_DIFFICULT_WORD_CHARS
+            || COMMON_WORDS.contains(&word.as_str())
+            || words.contains(&word)
+        {
+            continue;
+        }
+        words.push(word);
+    }
+    words
+}
+
+/// 单词本身及去掉常见屈折变化后的候选原形，用于在词典中精确匹配。
+pub fn word_forms(word: &str) -> Vec<String> {
+    let mut forms = vec![word.to_string()];
+    let mut push = |form: String| {
+        if form.len() >= 2 && !forms.contains(&form) {
+            forms.push(form);
+        }
+    };
+
+    if let Some(stem) = word.strip_suffix("ies") {
+        push(format!("{stem}y"));
+    }
+    if let Some(stem) = word.strip_suffix("es") {
+        push(stem.to_string());
+    }
+    if let Some(stem) = word.strip_suffix('s') {
+        push(stem.to_string());
+    }
+    if let Some(stem) = word.strip_suffix("ied") {
+        push(format!("{stem}y"));
+    }
+    if let Some(stem) = word.strip_suffix("ed") {
+        push(stem.to_string());
+        push(format!("{stem}e"));
+        push(undouble(stem));
+    }
+    if let Some(stem) = word.strip_suffix("ing") {
+        push(stem.to_string());
+        push(format!("{stem}e"));
+        push(undouble(stem));
+    }
+    if let Some(stem) = word.strip_suffix("ly") {
+        push(stem.to_string());
+    }
+    forms
+}
+
+/// `stopped` -> `stop`：去掉重复的末尾辅音。
+fn undouble(stem: &str) -> String {
+    let mut chars = stem.chars().rev();
+    match (chars.next(), chars.next()) {
+        (Some(last), Some(prev)) if last == prev && !"aeiou".contains(last) => {
+            stem[..stem.len() - last.len_utf8()].to_string()
+        }
+        _ => stem.to_string(),
+    }
+}
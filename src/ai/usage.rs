@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::ai::client::TokenUsage;
+use crate::app::storage::{load_data_json, save_data_json};
+use crate::app::time::current_month;
+use crate::app::ModelPrice;
+
+const USAGE_FILE: &str = "usage.json";
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct UsageTotals {
+    pub requests: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// 已知价格的请求的费用合计
+    pub cost: f64,
+    /// 模型没有配置价格、未计入 `cost` 的请求数
+    #[serde(default)]
+    pub unpriced_requests: u64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, usage: &TokenUsage, cost: Option<f64>) {
+        self.requests += 1;
+        self.prompt_tokens += usage.prompt_tokens;
+        self.completion_tokens += usage.completion_tokens;
+        match cost {
+            Some(cost) => self.cost += cost,
+            None => self.unpriced_requests += 1,
+        }
+    }
+
+    fn merge(&mut self, other: &UsageTotals) {
+        self.requests += other.requests;
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.cost += other.cost;
+        self.unpriced_requests += other.unpriced_requests;
+    }
+
+    /// 费用文字：全部请求都没有价格时为“未知价格”，部分没有时注明只是部分合计。
+    pub fn cost_text(&self, currency: &str) -> String {
+        if self.requests > 0 && self.unpriced_requests == self.requests {
+            "未知价格".to_string()
+        } else if self.unpriced_requests > 0 {
+            format!(
+                "{currency}{:.4} (另有 {} 次未知价格)",
+                self.cost, self.unpriced_requests
+            )
+        } else {
+            format!("{currency}{:.4}", self.cost)
+        }
+    }
+}
+
+/// 本次会话的用量只保存在内存中；按月、按模型的累计写入数据目录 `usage.json`。
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UsageLedger {
+    #[serde(default)]
+    months: BTreeMap<String, BTreeMap<String, UsageTotals>>,
+    #[serde(skip)]
+    session: UsageTotals,
+}
+
+impl UsageLedger {
+    pub fn load() -> Result<Self> {
+        load_data_json(USAGE_FILE)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        save_data_json(USAGE_FILE, self)
+    }
+
+    /// `cost` 为 `None` 表示模型没有配置价格。
+    pub fn record(&mut self, model: &str, usage: &TokenUsage, cost: Option<f64>) {
+        self.session.add(usage, cost);
+        self.months
+            .entry(current_month())
+            .or_default()
+            .entry(model.to_string())
+            .or_default()
+            .add(usage, cost);
+    }
+
+    pub fn session_totals(&self) -> UsageTotals {
+        self.session
+    }
+
+    pub fn current_month_totals(&self) -> UsageTotals {
+        let mut totals = UsageTotals::default();
+        for model_totals in self
+            .months
+            .get(&current_month())
+            .into_iter()
+            .flat_map(|m| m.values())
+        {
+            totals.merge(model_totals);
+        }
+        totals
+    }
+}
+
+pub fn estimate_cost(usage: &TokenUsage, price: Option<&ModelPrice>) -> Option<f64> {
+    let price = price?;
+    Some(
+        (usage.prompt_tokens as f64 * price.input_per_million
+            + usage.completion_tokens as f64 * price.output_per_million)
+            / 1_000_000.0,
+    )
+}
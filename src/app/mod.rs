@@ -1,20 +1,150 @@
+pub mod storage;
+pub mod time;
+
+use std::collections::{BTreeMap, HashMap};
+use std::env;
 use std::fs;
 use std::path::PathBuf;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::ai::prompt::{
+    default_prompt_templates, default_system_prompt, default_translation_template,
+    is_legacy_system_prompt, PromptTemplate, TRANSLATION_TEMPLATE_NAME,
+};
+
+/// 设置后优先于配置文件中的所有 API Key 来源。
+pub const API_KEY_OVERRIDE_ENV: &str = "DICT_TUI_API_KEY";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub api_endpoint: String,
+    #[serde(default)]
     pub api_key: String,
+    /// 从该环境变量读取 API Key。
+    #[serde(default)]
+    pub api_key_env: String,
+    /// 从该文件读取 API Key，支持 `~/` 开头的路径。
+    #[serde(default)]
+    pub api_key_file: String,
     pub answer_language: String,
     pub system_prompt: String,
+    #[serde(default = "default_prompt_templates")]
+    pub prompt_templates: Vec<PromptTemplate>,
     pub model: String,
     #[serde(default)]
     pub enable_web_search: bool,
     #[serde(default)]
     pub enable_javascript: bool,
+    #[serde(default)]
+    pub structured_answers: bool,
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    #[serde(default = "default_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// HTTP/HTTPS/SOCKS5 代理地址，留空时使用系统代理环境变量。
+    #[serde(default)]
+    pub proxy: String,
+    /// 不走 `proxy` 的主机列表，逗号分隔。
+    #[serde(default)]
+    pub no_proxy: String,
+    /// 额外信任的 CA 证书 (PEM，可包含多个证书)。
+    #[serde(default)]
+    pub ca_cert_path: String,
+    #[serde(default)]
+    pub custom_headers: BTreeMap<String, String>,
+    /// 导出 Anki 卡片时背面使用词典原始 HTML，否则使用清理后的纯文本。
+    #[serde(default)]
+    pub anki_html_back: bool,
+    /// 详情栏停留超过该秒数才记入查询历史，0 表示不记录停留。
+    #[serde(default = "default_history_dwell_secs")]
+    pub history_dwell_secs: u64,
+    /// 搜索页快捷键，操作名到按键列表，未出现的操作使用默认按键。
+    #[serde(default)]
+    pub keymap: BTreeMap<String, Vec<String>>,
+    /// 界面主题: dark、light 或 mono。
+    #[serde(default = "default_theme")]
+    pub theme: String,
+    /// 按槽位覆盖主题样式，如 `{"headword": "bold yellow"}`。
+    #[serde(default)]
+    pub theme_overrides: BTreeMap<String, String>,
+    /// OSC 52 之外再用来复制的外部命令，如 `wl-copy`、`xclip -selection clipboard`。
+    #[serde(default)]
+    pub clipboard_command: String,
+    #[serde(default)]
+    pub layout_mode: LayoutMode,
+    /// `auto` 布局下窗口窄于该列数时上下排布。
+    #[serde(default = "default_stack_below_width")]
+    pub stack_below_width: u16,
+    /// 结果列表占宽度 (上下排布时为高度) 的百分比。
+    #[serde(default = "default_list_percent")]
+    pub list_percent: u16,
+    /// 只显示详情栏。
+    #[serde(default)]
+    pub detail_zoom: bool,
+    #[serde(default = "default_model_prices")]
+    pub model_prices: HashMap<String, ModelPrice>,
+    #[serde(default = "default_price_currency")]
+    pub price_currency: String,
+}
+
+/// 搜索页结果列表与详情栏的排布方式。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutMode {
+    /// 按窗口宽度在左右和上下排布之间切换
+    #[default]
+    Auto,
+    Horizontal,
+    Vertical,
+}
+
+impl LayoutMode {
+    pub const ALL: [LayoutMode; 3] = [
+        LayoutMode::Auto,
+        LayoutMode::Horizontal,
+        LayoutMode::Vertical,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LayoutMode::Auto => "auto",
+            LayoutMode::Horizontal => "horizontal",
+            LayoutMode::Vertical => "vertical",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LayoutMode::Auto => "自动",
+            LayoutMode::Horizontal => "左右",
+            LayoutMode::Vertical => "上下",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            LayoutMode::Auto => LayoutMode::Horizontal,
+            LayoutMode::Horizontal => LayoutMode::Vertical,
+            LayoutMode::Vertical => LayoutMode::Auto,
+        }
+    }
+}
+
+/// 每百万 token 的价格。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
 }
 
 impl Default for Config {
@@ -22,15 +152,80 @@ impl Default for Config {
         Self {
             api_endpoint: "https://api.openai.com/v1/chat/completions".to_string(),
             api_key: String::new(),
+            api_key_env: String::new(),
+            api_key_file: String::new(),
             answer_language: "中文".to_string(),
             model: "gpt-4o-mini".to_string(),
-            system_prompt: crate::ai::prompt::default_system_prompt().to_string(),
+            system_prompt: default_system_prompt().to_string(),
+            prompt_templates: default_prompt_templates(),
             enable_web_search: false,
             enable_javascript: false,
+            structured_answers: false,
+            request_timeout_secs: default_request_timeout_secs(),
+            connect_timeout_secs: default_connect_timeout_secs(),
+            max_retries: default_max_retries(),
+            proxy: String::new(),
+            no_proxy: String::new(),
+            ca_cert_path: String::new(),
+            custom_headers: BTreeMap::new(),
+            anki_html_back: false,
+            history_dwell_secs: default_history_dwell_secs(),
+            keymap: BTreeMap::new(),
+            theme: default_theme(),
+            theme_overrides: BTreeMap::new(),
+            clipboard_command: String::new(),
+            layout_mode: LayoutMode::default(),
+            stack_below_width: default_stack_below_width(),
+            list_percent: default_list_percent(),
+            detail_zoom: false,
+            model_prices: default_model_prices(),
+            price_currency: default_price_currency(),
         }
     }
 }
 
+fn default_request_timeout_secs() -> u64 {
+    120
+}
+
+fn default_connect_timeout_secs() -> u64 {
+    10
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+fn default_history_dwell_secs() -> u64 {
+    3
+}
+
+fn default_stack_below_width() -> u16 {
+    80
+}
+
+fn default_list_percent() -> u16 {
+    35
+}
+
+fn default_theme() -> String {
+    crate::ui::theme::DEFAULT_THEME.to_string()
+}
+
+fn default_model_prices() -> HashMap<String, ModelPrice> {
+    HashMap::from([(
+        "gpt-4o-mini".to_string(),
+        ModelPrice {
+            input_per_million: 0.15,
+            output_per_million: 0.6,
+        },
+    )])
+}
+
+fn default_price_currency() -> String {
+    "$".to_string()
+}
+
 impl Config {
     pub fn load() -> Result<Self> {
         let config_path = config_file_path()?;
@@ -39,8 +234,87 @@ impl Config {
         }
         let content = fs::read_to_string(&config_path)
             .with_context(|| format!("读取配置文件失败: {}", config_path.display()))?;
-        serde_json::from_str(&content)
-            .with_context(|| format!("解析配置文件失败: {}", config_path.display()))
+        let mut config: Config = serde_json::from_str(&content)
+            .with_context(|| format!("解析配置文件失败: {}", config_path.display()))?;
+        // 旧版默认提示词要求不用 Markdown，与现在的渲染方式相反；改过的提示词保持不变
+        if is_legacy_system_prompt(&config.system_prompt) {
+            config.system_prompt = default_system_prompt().to_string();
+        }
+        Ok(config)
+    }
+
+    /// 按序号取单词查询的提示词模板；序号无效或指向句子翻译模板时退回第一个单词模板，
+    /// 配置中没有单词模板时退回内置的第一个模板。
+    pub fn prompt_template(&self, index: usize) -> PromptTemplate {
+        let word_templates = self.word_template_indexes();
+        let index = if word_templates.contains(&index) {
+            Some(index)
+        } else {
+            word_templates.first().copied()
+        };
+        index
+            .and_then(|index| self.prompt_templates.get(index))
+            .cloned()
+            .unwrap_or_else(|| default_prompt_templates().remove(0))
+    }
+
+    /// 单词查询可选的模板序号；句子翻译模板只用于句子输入，不在其中。
+    pub fn word_template_indexes(&self) -> Vec<usize> {
+        self.prompt_templates
+            .iter()
+            .enumerate()
+            .filter(|(_, template)| template.name != TRANSLATION_TEMPLATE_NAME)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// 按优先级解析 API Key：`DICT_TUI_API_KEY` > `api_key_env` > `api_key_file` > `api_key`。
+    pub fn resolve_api_key(&self) -> Result<String> {
+        if let Some(key) = non_empty_env(API_KEY_OVERRIDE_ENV) {
+            return Ok(key);
+        }
+
+        let env_name = self.api_key_env.trim();
+        if !env_name.is_empty() {
+            return non_empty_env(env_name)
+                .with_context(|| format!("环境变量 {env_name} 未设置或为空"));
+        }
+
+        let file = self.api_key_file.trim();
+        if !file.is_empty() {
+            let path = expand_home(file);
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("读取 API Key 文件失败: {}", path.display()))?;
+            let key = content.trim();
+            if key.is_empty() {
+                bail!("API Key 文件为空: {}", path.display());
+            }
+            return Ok(key.to_string());
+        }
+
+        Ok(self.api_key.trim().to_string())
+    }
+
+    /// 当 API Key 不是直接写在配置文件中时，返回其来源说明。
+    pub fn api_key_source(&self) -> Option<String> {
+        if non_empty_env(API_KEY_OVERRIDE_ENV).is_some() {
+            Some(format!("环境变量 {API_KEY_OVERRIDE_ENV}"))
+        } else if !self.api_key_env.trim().is_empty() {
+            Some(format!("环境变量 {}", self.api_key_env.trim()))
+        } else if !self.api_key_file.trim().is_empty() {
+            Some(format!("文件 {}", self.api_key_file.trim()))
+        } else {
+            None
+        }
+    }
+
+    /// 句子翻译模板：优先使用配置中同名的模板，以便用户自行修改。
+    pub fn translation_template(&self) -> PromptTemplate {
+        self.prompt_templates
+            .iter()
+            .find(|template| template.name == TRANSLATION_TEMPLATE_NAME)
+            .cloned()
+            .unwrap_or_else(default_translation_template)
     }
 
     pub fn save(&self) -> Result<()> {
@@ -50,7 +324,7 @@ impl Config {
                 .with_context(|| format!("创建配置目录失败: {}", parent.display()))?;
         }
         let content = serde_json::to_string_pretty(self).context("序列化配置失败")?;
-        fs::write(&config_path, content)
+        storage::write_private_file(&config_path, &content)
             .with_context(|| format!("写入配置文件失败: {}", config_path.display()))?;
         Ok(())
     }
@@ -62,3 +336,17 @@ fn config_file_path() -> Result<PathBuf> {
     path.push("config.json");
     Ok(path)
 }
+
+fn non_empty_env(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
@@ -0,0 +1,79 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::app::time::now_secs;
+
+pub fn data_file_path(name: &str) -> Result<PathBuf> {
+    let mut path = dirs::data_dir().context("无法获取数据目录")?;
+    path.push("dict-tui");
+    path.push(name);
+    Ok(path)
+}
+
+/// 文件无法解析时先把它改名为 `<name>.<时间戳>.bak` 再返回错误，调用方退回默认值后
+/// 再次保存也不会覆盖原有数据。
+pub fn load_data_json<T>(name: &str) -> Result<T>
+where
+    T: DeserializeOwned + Default,
+{
+    let path = data_file_path(name)?;
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("读取数据文件失败: {}", path.display()))?;
+    match serde_json::from_str(&content) {
+        Ok(value) => Ok(value),
+        Err(err) => {
+            let backup = path.with_file_name(format!("{name}.{}.bak", now_secs()));
+            fs::rename(&path, &backup).with_context(|| {
+                format!("解析数据文件失败 ({err})，且无法移到 {}", backup.display())
+            })?;
+            bail!(
+                "解析数据文件失败: {} ({err})，原文件已移到 {}",
+                path.display(),
+                backup.display()
+            )
+        }
+    }
+}
+
+/// 先写入同目录下的临时文件再改名覆盖，写入中途出错不会留下半个文件。
+pub fn save_data_json<T: Serialize>(name: &str, value: &T) -> Result<()> {
+    let path = data_file_path(name)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("创建数据目录失败: {}", parent.display()))?;
+    }
+    let content = serde_json::to_string_pretty(value).context("序列化数据失败")?;
+    let temp = path.with_file_name(format!("{name}.tmp"));
+    fs::write(&temp, content).with_context(|| format!("写入数据文件失败: {}", temp.display()))?;
+    fs::rename(&temp, &path).with_context(|| format!("替换数据文件失败: {}", path.display()))?;
+    Ok(())
+}
+
+/// 写入只有当前用户可读写的文件 (Unix 下权限为 0600)，已存在的文件也会收紧权限。
+pub fn write_private_file(path: &Path, content: &str) -> Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+                .with_context(|| format!("修改文件权限失败: {}", path.display()))?;
+        }
+    }
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("打开文件失败: {}", path.display()))?;
+    file.write_all(content.as_bytes())
+        .with_context(|| format!("写入文件失败: {}", path.display()))?;
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::app::storage::{load_data_json, save_data_json};
+use crate::app::time::now_secs;
+use crate::app::Config;
+
+const AI_CACHE_FILE: &str = "ai_cache.json";
+/// 超出后丢弃最早的回答，限制文件大小和每次保存的开销。
+const MAX_AI_CACHE_RECORDS: usize = 2000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AiCacheKey {
+    pub query: String,
+    pub context_hash: String,
+    pub model: String,
+    pub prompt_hash: String,
+}
+
+impl AiCacheKey {
+    pub fn new(query: &str, context: &str, user_prompt: &str, config: &Config) -> Self {
+        let prompt = format!(
+            "{}\n{}\n{}\n{}",
+            config.system_prompt, user_prompt, config.enable_web_search, config.structured_answers
+        );
+        Self {
+            query: query.trim().to_lowercase(),
+            context_hash: stable_hash(context.trim()),
+            model: config.model.clone(),
+            prompt_hash: stable_hash(&prompt),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AiCacheRecord {
+    pub key: AiCacheKey,
+    pub query: String,
+    pub context: String,
+    #[serde(default)]
+    pub template: String,
+    pub answer: String,
+    pub created_at: u64,
+}
+
+impl AiCacheRecord {
+    pub fn new(
+        key: AiCacheKey,
+        query: String,
+        context: String,
+        template: String,
+        answer: String,
+    ) -> Self {
+        Self {
+            key,
+            query,
+            context,
+            template,
+            answer,
+            created_at: now_secs(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AiAnswerCache {
+    records: Vec<AiCacheRecord>,
+}
+
+impl AiAnswerCache {
+    pub fn load() -> Result<Self> {
+        load_data_json(AI_CACHE_FILE)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        save_data_json(AI_CACHE_FILE, self)
+    }
+
+    pub fn get(&self, key: &AiCacheKey) -> Option<&AiCacheRecord> {
+        self.records.iter().find(|record| &record.key == key)
+    }
+
+    pub fn insert(&mut self, record: AiCacheRecord) {
+        self.records.retain(|existing| existing.key != record.key);
+        self.records.push(record);
+        let overflow = self.records.len().saturating_sub(MAX_AI_CACHE_RECORDS);
+        self.records.drain(..overflow);
+    }
+
+    /// 某个查询词最近一次的回答，不区分上下文和模板。
+    pub fn latest_for_query(&self, query: &str) -> Option<&AiCacheRecord> {
+        let query = query.trim().to_lowercase();
+        self.records
+            .iter()
+            .rev()
+            .find(|record| record.key.query == query)
+    }
+
+    pub fn records(&self) -> &[AiCacheRecord] {
+        &self.records
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<AiCacheRecord> {
+        (index < self.records.len()).then(|| self.records.remove(index))
+    }
+
+    /// 按时间倒序返回匹配过滤词的记录索引，过滤词同时匹配查询词和回答内容。
+    pub fn search(&self, filter: &str) -> Vec<usize> {
+        let needle = filter.trim().to_lowercase();
+        self.records
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, record)| {
+                needle.is_empty()
+                    || record.key.query.contains(&needle)
+                    || record.answer.to_lowercase().contains(&needle)
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+}
+
+/// FNV-1a 哈希：结果需要跨版本写入磁盘，不能使用 `DefaultHasher`。
+fn stable_hash(text: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in text.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{hash:016x}")
+}
@@ -25,23 +25,38 @@ impl DefinitionCache {
         }
     }
 
-    pub fn get_or_load(&mut self, dict: &mut DictionaryStore, entry_idx: usize) -> Result<String> {
+    pub fn get_or_load(&mut self, dict: &DictionaryStore, entry_idx: usize) -> Result<String> {
+        if let Some(definition) = self.get(dict, entry_idx) {
+            return Ok(definition);
+        }
+
+        let definition = dict.fetch_definition(entry_idx)?;
+        self.put(dict, entry_idx, definition.clone());
+        Ok(definition)
+    }
+
+    /// 只查缓存，不读取词典。
+    pub fn get(&self, dict: &DictionaryStore, entry_idx: usize) -> Option<String> {
+        let key = Self::key(dict, entry_idx).ok()?;
+        self.map.get(&key).cloned()
+    }
+
+    /// 存入在别处 (如后台线程) 读取的词条内容。
+    pub fn put(&mut self, dict: &DictionaryStore, entry_idx: usize, definition: String) {
+        if let Ok(key) = Self::key(dict, entry_idx) {
+            self.insert(key, definition);
+        }
+    }
+
+    fn key(dict: &DictionaryStore, entry_idx: usize) -> Result<DefinitionKey> {
         let entry = dict
             .entries
             .get(entry_idx)
             .with_context(|| format!("无效词条索引: {}", entry_idx))?;
-        let key = DefinitionKey {
+        Ok(DefinitionKey {
             dict_idx: entry.dict_idx,
             keyword_idx: entry.keyword_idx,
-        };
-
-        if let Some(definition) = self.map.get(&key) {
-            return Ok(definition.clone());
-        }
-
-        let definition = dict.fetch_definition(entry_idx)?;
-        self.insert(key, definition.clone());
-        Ok(definition)
+        })
     }
 
     fn insert(&mut self, key: DefinitionKey, value: String) {
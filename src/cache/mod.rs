@@ -1,8 +1,10 @@
 pub mod query_cache;
 pub mod definition_cache;
+pub mod ai_cache;
 
 pub use query_cache::QueryResultCache;
 pub use definition_cache::DefinitionCache;
+pub use ai_cache::{AiAnswerCache, AiCacheKey, AiCacheRecord};
 
 pub const SEARCH_CACHE_CAPACITY: usize = 2048;
 pub const DEFINITION_CACHE_CAPACITY: usize = 4096;
\ No newline at end of file
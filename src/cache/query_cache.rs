@@ -17,26 +17,39 @@ impl QueryResultCache {
         }
     }
 
-    pub fn query(&mut self, dict: &DictionaryStore, query: &str) -> Vec<usize> {
+    /// `cancelled` 返回 `true` 时放弃查询并返回 `None`，半途的结果不进缓存。
+    pub fn query(
+        &mut self,
+        dict: &DictionaryStore,
+        query: &str,
+        cancelled: &dyn Fn() -> bool,
+    ) -> Option<Vec<usize>> {
         let needle = query.trim().to_lowercase();
         if needle.is_empty() {
-            return Vec::new();
+            return Some(Vec::new());
         }
 
         if let Some(result) = self.map.get(&needle) {
-            return result.clone();
+            return Some(result.clone());
         }
 
-        let cached_prefix_result = self.find_longest_prefix_result(&needle);
-        let result = dict.search(&needle, cached_prefix_result.as_deref());
+        // `@名称` 过滤前缀不参与前缀复用，只在词的部分逐字缩短
+        let word = DictionaryStore::split_source_filter(&needle).1;
+        if word.is_empty() {
+            return Some(Vec::new());
+        }
+        let word_start = needle.len() - word.len();
+
+        let cached_prefix_result = self.find_longest_prefix_result(&needle, word_start);
+        let result = dict.search_filtered(&needle, cached_prefix_result.as_deref(), cancelled)?;
         self.insert(needle, result.clone());
-        result
+        Some(result)
     }
 
-    fn find_longest_prefix_result(&self, needle: &str) -> Option<Vec<usize>> {
+    fn find_longest_prefix_result(&self, needle: &str, word_start: usize) -> Option<Vec<usize>> {
         for (idx, _) in needle.char_indices().rev() {
             let prefix = &needle[..idx];
-            if prefix.is_empty() {
+            if idx <= word_start {
                 break;
             }
             if let Some(result) = self.map.get(prefix) {
@@ -1,20 +1,30 @@
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
 
 use anyhow::{Context, Result, bail};
 use rust_mdict::Mdx;
 
 use super::entry::Entry;
 
+/// 搜索时每扫描这么多词条检查一次是否已被新的查询取代。
+const CANCEL_CHECK_INTERVAL: usize = 16_384;
+
 pub struct DictionarySource {
-    pub mdx: Mdx,
+    pub name: String,
+    /// 读取词条需要独占访问，界面线程与后台线程共用
+    pub mdx: Mutex<Mdx>,
     pub keywords: Vec<rust_mdict::KeyWordItem>,
     pub asset_dir: PathBuf,
 }
 
+/// 词条和词典都放在 `Arc` 中，克隆后可交给后台线程使用。
+#[derive(Clone)]
 pub struct DictionaryStore {
-    pub entries: Vec<Entry>,
-    pub sources: Vec<DictionarySource>,
+    pub entries: Arc<Vec<Entry>>,
+    pub sources: Arc<Vec<DictionarySource>>,
+    /// 每个词典的词条在 `entries` 中的位置 (升序)，`@名称` 过滤时只扫描这些词条
+    source_entries: Arc<Vec<Vec<usize>>>,
 }
 
 impl DictionaryStore {
@@ -66,42 +76,94 @@ impl DictionaryStore {
             );
         }
 
+        Ok(Self::from_parts(entries, sources))
+    }
+
+    /// 按 `word_lower` 排序词条，并记录每个词典的词条位置。
+    fn from_parts(mut entries: Vec<Entry>, sources: Vec<DictionarySource>) -> Self {
         entries.sort_by(|a, b| a.word_lower.cmp(&b.word_lower));
-        Ok(Self { entries, sources })
+        let mut source_entries = vec![Vec::new(); sources.len()];
+        for (index, entry) in entries.iter().enumerate() {
+            if let Some(indexes) = source_entries.get_mut(entry.dict_idx) {
+                indexes.push(index);
+            }
+        }
+        Self { entries: Arc::new(entries), sources: Arc::new(sources), source_entries: Arc::new(source_entries) }
+    }
+
+    /// 查询开头的 `@名称` 把结果限定在名称包含该文字 (不区分大小写) 的词典，如 `@oxford run`。
+    /// 返回 (过滤文字, 其余查询)。
+    pub fn split_source_filter(query: &str) -> (Option<&str>, &str) {
+        let query = query.trim_start();
+        match query.strip_prefix('@') {
+            Some(rest) => {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                (Some(&rest[..end]), rest[end..].trim_start())
+            }
+            None => (None, query),
+        }
+    }
+
+    /// 名称包含 `filter` 的词典序号。
+    pub fn matching_sources(&self, filter: &str) -> Vec<usize> {
+        let filter = filter.to_lowercase();
+        self.sources
+            .iter()
+            .enumerate()
+            .filter(|(_, source)| source.name.to_lowercase().contains(&filter))
+            .map(|(index, _)| index)
+            .collect()
     }
 
-    pub fn search(&self, needle: &str, candidates: Option<&[usize]>) -> Vec<usize> {
+    /// 按 [`split_source_filter`](Self::split_source_filter) 的规则解析 `@名称` 前缀后查询，
+    /// 可中途放弃，见 [`search_until`](Self::search_until)。
+    /// 有过滤前缀时只扫描匹配词典的词条，而不是搜索整个词库后再筛选。
+    pub fn search_filtered(&self, needle: &str, candidates: Option<&[usize]>, cancelled: &dyn Fn() -> bool) -> Option<Vec<usize>> {
+        let (filter, word) = Self::split_source_filter(needle);
+        let Some(filter) = filter else {
+            return self.search_until(word, candidates, cancelled);
+        };
+        let sources = self.matching_sources(filter);
+        let scoped: Vec<usize> = match candidates {
+            Some(indexes) => indexes.iter().copied().filter(|&index| sources.contains(&self.entries[index].dict_idx)).collect(),
+            None => {
+                let mut indexes: Vec<usize> = sources.iter().flat_map(|&source| self.source_entries[source].iter().copied()).collect();
+                // 多个词典时合并为 `entries` 的顺序，结果排列与不过滤时一致
+                if sources.len() > 1 {
+                    indexes.sort_unstable();
+                }
+                indexes
+            }
+        };
+        self.search_until(word.trim_end(), Some(&scoped), cancelled)
+    }
+
+    /// 三级匹配搜索 (完全一致、前缀、包含)，每扫描一批词条检查一次 `cancelled`，
+    /// 返回 `None` 表示查询已被放弃。
+    pub fn search_until(&self, needle: &str, candidates: Option<&[usize]>, cancelled: &dyn Fn() -> bool) -> Option<Vec<usize>> {
         if needle.is_empty() {
-            return Vec::new();
+            return Some(Vec::new());
         }
 
         let mut exact = Vec::new();
         let mut prefix = Vec::new();
         let mut contains = Vec::new();
 
-        match candidates {
-            Some(indexes) => {
-                for &index in indexes {
-                    let entry = &self.entries[index];
-                    if entry.word_lower == needle {
-                        exact.push(index);
-                    } else if entry.word_lower.starts_with(needle) {
-                        prefix.push(index);
-                    } else if entry.word_lower.contains(needle) {
-                        contains.push(index);
-                    }
-                }
+        let indexes: Box<dyn Iterator<Item = usize>> = match candidates {
+            Some(indexes) => Box::new(indexes.iter().copied()),
+            None => Box::new(0..self.entries.len()),
+        };
+        for (scanned, index) in indexes.enumerate() {
+            if scanned % CANCEL_CHECK_INTERVAL == 0 && cancelled() {
+                return None;
             }
-            None => {
-                for (index, entry) in self.entries.iter().enumerate() {
-                    if entry.word_lower == needle {
-                        exact.push(index);
-                    } else if entry.word_lower.starts_with(needle) {
-                        prefix.push(index);
-                    } else if entry.word_lower.contains(needle) {
-                        contains.push(index);
-                    }
-                }
+            let entry = &self.entries[index];
+            if entry.word_lower == needle {
+                exact.push(index);
+            } else if entry.word_lower.starts_with(needle) {
+                prefix.push(index);
+            } else if entry.word_lower.contains(needle) {
+                contains.push(index);
             }
         }
 
@@ -109,15 +171,28 @@ impl DictionaryStore {
         merged.extend(exact);
         merged.extend(prefix);
         merged.extend(contains);
-        merged
+        Some(merged)
     }
 
-    pub fn fetch_definition(&mut self, entry_idx: usize) -> Result<String> {
+    /// 与词头完全一致 (不区分大小写) 的词条，可限定来源词典。
+    /// `entries` 按 `word_lower` 排序，二分查找到同名词条的起点，不扫描整个词库。
+    pub fn find_exact(&self, word: &str, source: Option<&str>) -> Option<usize> {
+        let needle = word.trim().to_lowercase();
+        let start = self.entries.partition_point(|entry| entry.word_lower < needle);
+        self.entries[start..]
+            .iter()
+            .take_while(|entry| entry.word_lower == needle)
+            .position(|entry| source.is_none_or(|source| entry.source == source))
+            .map(|offset| start + offset)
+    }
+
+    pub fn fetch_definition(&self, entry_idx: usize) -> Result<String> {
         let entry = self.entries.get(entry_idx).with_context(|| format!("无效词条索引: {}", entry_idx))?;
-        let source = self.sources.get_mut(entry.dict_idx).with_context(|| format!("无效词典索引: {}", entry.dict_idx))?;
+        let source = self.sources.get(entry.dict_idx).with_context(|| format!("无效词典索引: {}", entry.dict_idx))?;
         let keyword = source.keywords.get(entry.keyword_idx).with_context(|| format!("无效关键词索引: {}", entry.keyword_idx))?;
 
-        source.mdx.fetch(keyword).map(|r| r.definition).with_context(|| format!("无法读取词条定义: {}", entry.word))
+        let mut mdx = source.mdx.lock().unwrap_or_else(PoisonError::into_inner);
+        mdx.fetch(keyword).map(|r| r.definition).with_context(|| format!("无法读取词条定义: {}", entry.word))
     }
 
     pub fn entry_web_context(&self, entry_idx: usize) -> Result<(String, PathBuf)> {
@@ -161,7 +236,7 @@ impl DictionaryStore {
 
         let asset_dir = path.parent().map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from("."));
 
-        Ok((DictionarySource { mdx, keywords, asset_dir }, entries))
+        Ok((DictionarySource { name: source.to_string(), mdx: Mutex::new(mdx), keywords, asset_dir }, entries))
     }
 
     fn dictionary_name_from_folder(root: &Path, mdx_path: &Path) -> String {
@@ -182,4 +257,84 @@ impl DictionaryStore {
             .or_else(|| mdx_path.file_stem().and_then(|n| n.to_str()).map(ToString::to_string))
             .unwrap_or_else(|| "unknown".to_string())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 只有词条、没有实际词典文件的词库，足够测试查找逻辑。
+    fn store(words: &[(&str, &str)]) -> DictionaryStore {
+        let entries = words
+            .iter()
+            .enumerate()
+            .map(|(index, (word, source))| {
+                Entry::new(word.to_string(), 0, index, source.to_string())
+            })
+            .collect();
+        DictionaryStore::from_parts(entries, Vec::new())
+    }
+
+    fn words(store: &DictionaryStore, indexes: &[usize]) -> Vec<String> {
+        indexes
+            .iter()
+            .map(|&index| store.entries[index].word.clone())
+            .collect()
+    }
+
+    #[test]
+    fn split_source_filter_parses_prefix() {
+        assert_eq!(DictionaryStore::split_source_filter("run"), (None, "run"));
+        assert_eq!(
+            DictionaryStore::split_source_filter("  @oxford  run fast"),
+            (Some("oxford"), "run fast")
+        );
+        assert_eq!(
+            DictionaryStore::split_source_filter("@oxford"),
+            (Some("oxford"), "")
+        );
+        assert_eq!(DictionaryStore::split_source_filter("@"), (Some(""), ""));
+        assert_eq!(
+            DictionaryStore::split_source_filter("e@mail"),
+            (None, "e@mail")
+        );
+    }
+
+    #[test]
+    fn search_orders_exact_prefix_contains() {
+        let store = store(&[("rerun", "A"), ("running", "A"), ("run", "A"), ("Run", "B")]);
+        let found = store.search_until("run", None, &|| false).unwrap();
+        assert_eq!(words(&store, &found), ["run", "Run", "running", "rerun"]);
+    }
+
+    #[test]
+    fn search_stops_when_cancelled() {
+        let store = store(&[("run", "A")]);
+        assert_eq!(store.search_until("run", None, &|| true), None);
+    }
+
+    #[test]
+    fn filter_without_matching_dictionary_finds_nothing() {
+        let store = store(&[("run", "A")]);
+        assert_eq!(
+            store.search_filtered("@oxford run", None, &|| false),
+            Some(Vec::new())
+        );
+        assert_eq!(
+            store
+                .search_filtered("run", None, &|| false)
+                .map(|found| found.len()),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn find_exact_ignores_case_and_checks_source() {
+        let store = store(&[("apple", "A"), ("Apple", "B"), ("apples", "A")]);
+        let found = store.find_exact(" APPLE ", Some("B")).unwrap();
+        assert_eq!(store.entries[found].source, "B");
+        assert!(store.find_exact("apple", None).is_some());
+        assert_eq!(store.find_exact("appl", None), None);
+        assert_eq!(store.find_exact("apple", Some("C")), None);
+    }
+}
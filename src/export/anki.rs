@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+
+use crate::app::storage::data_file_path;
+use crate::cache::DefinitionCache;
+use crate::dict::DictionaryStore;
+use crate::history::WordStats;
+use crate::notebook::{Notebook, NotebookEntry};
+use crate::render::browser::{
+    extract_referenced_resources, rewrite_resource_refs, strip_scripts, strip_sound_scheme,
+};
+use crate::render::html_to_plain_text;
+
+const EXPORT_DIR: &str = "anki";
+const NOTES_FILE: &str = "dict-tui-notes.tsv";
+const HISTORY_FILE: &str = "dict-tui-history.tsv";
+const HISTORY_TAG: &str = "history";
+const MEDIA_DIR: &str = "media";
+const EXPORT_TAG: &str = "dict-tui";
+const AUDIO_EXTENSIONS: &[&str] = &["mp3", "wav", "ogg", "spx", "m4a", "aac"];
+
+pub struct AnkiExport {
+    pub notes_path: PathBuf,
+    pub media_dir: PathBuf,
+    pub cards: usize,
+    pub media_files: usize,
+}
+
+impl AnkiExport {
+    pub fn summary(&self) -> String {
+        format!(
+            "已导出 {} 张卡片: {} | 新增媒体 {} 个，请复制到 Anki 的 collection.media: {}",
+            self.cards,
+            self.notes_path.display(),
+            self.media_files,
+            self.media_dir.display()
+        )
+    }
+}
+
+struct Card {
+    front: String,
+    back: String,
+    tags: Vec<String>,
+}
+
+/// 把生词本导出为 Anki 可导入的 TSV (正面词头，背面释义)，
+/// 词条引用的 MDD 图片和音频提取到 `media` 目录。
+pub fn export_notebook(
+    notebook: &Notebook,
+    store: &DictionaryStore,
+    definition_cache: &mut DefinitionCache,
+    html_back: bool,
+) -> Result<AnkiExport> {
+    export_entries(
+        notebook.entries(),
+        NOTES_FILE,
+        store,
+        definition_cache,
+        html_back,
+    )
+}
+
+/// 导出查询历史中的单词，词典中找不到的词 (如翻译过的句子) 会被跳过。
+pub fn export_history(
+    words: &[WordStats],
+    store: &DictionaryStore,
+    definition_cache: &mut DefinitionCache,
+    html_back: bool,
+) -> Result<AnkiExport> {
+    let entries: Vec<NotebookEntry> = words
+        .iter()
+        .filter_map(|stats| {
+            // 记录的来源词典已不存在时退回任意来源
+            let idx = match stats.source.as_str() {
+                "" => store.find_exact(&stats.word, None),
+                source => store
+                    .find_exact(&stats.word, Some(source))
+                    .or_else(|| store.find_exact(&stats.word, None)),
+            }?;
+            let entry = &store.entries[idx];
+            let mut card = NotebookEntry::new(
+                entry.word.clone(),
+                entry.source.clone(),
+                String::new(),
+                None,
+            );
+            card.tags.push(HISTORY_TAG.to_string());
+            Some(card)
+        })
+        .collect();
+    export_entries(&entries, HISTORY_FILE, store, definition_cache, html_back)
+}
+
+fn export_entries(
+    entries: &[NotebookEntry],
+    file_name: &str,
+    store: &DictionaryStore,
+    definition_cache: &mut DefinitionCache,
+    html_back: bool,
+) -> Result<AnkiExport> {
+    let export_dir = data_file_path(EXPORT_DIR)?;
+    let media_dir = export_dir.join(MEDIA_DIR);
+    fs::create_dir_all(&media_dir)
+        .with_context(|| format!("创建 Anki 媒体目录失败: {}", media_dir.display()))?;
+
+    let mut media = MediaCollector::new(media_dir.clone());
+    let mut cards = Vec::new();
+    for entry in entries {
+        let back = build_back(entry, store, definition_cache, html_back, &mut media)?;
+        cards.push(Card {
+            front: escape_html(&entry.word),
+            back,
+            tags: entry.tags.clone(),
+        });
+    }
+    media.cleanup();
+
+    let notes_path = export_dir.join(file_name);
+    fs::write(&notes_path, cards_to_tsv(&cards))
+        .with_context(|| format!("写入 Anki 导出文件失败: {}", notes_path.display()))?;
+
+    Ok(AnkiExport {
+        notes_path,
+        media_dir,
+        cards: cards.len(),
+        media_files: media.copied,
+    })
+}
+
+fn build_back(
+    entry: &NotebookEntry,
+    store: &DictionaryStore,
+    definition_cache: &mut DefinitionCache,
+    html_back: bool,
+    media: &mut MediaCollector,
+) -> Result<String> {
+    let found = store
+        .find_exact(&entry.word, Some(&entry.source))
+        .and_then(|idx| {
+            let raw = definition_cache.get_or_load(store, idx).ok()?;
+            let (_, asset_dir) = store.entry_web_context(idx).ok()?;
+            Some((raw, asset_dir))
+        });
+
+    let mut back = match &found {
+        Some((raw, asset_dir)) => {
+            let files = media.collect(raw, asset_dir, &entry.source)?;
+            let mut back = if html_back {
+                let replacements = files
+                    .iter()
+                    .map(|(reference, name)| (reference.clone(), name.clone()))
+                    .collect();
+                single_line(&rewrite_resource_refs(&strip_scripts(raw), &replacements))
+            } else {
+                text_to_html(&html_to_plain_text(raw))
+            };
+            // Anki 只能通过 [sound:] 标记播放音频
+            let mut sounds: Vec<&String> = files
+                .iter()
+                .filter(|(reference, _)| is_audio(reference))
+                .map(|(_, name)| name)
+                .collect();
+            sounds.sort();
+            sounds.dedup();
+            for name in sounds {
+                back.push_str(&format!(" [sound:{name}]"));
+            }
+            back
+        }
+        None => text_to_html(&entry.definition),
+    };
+
+    if !entry.note.is_empty() {
+        back.push_str(&format!("<hr>{}", text_to_html(&entry.note)));
+    }
+    Ok(back)
+}
+
+/// 先把资源提取到临时目录，再以扁平的文件名复制到 Anki 媒体目录。
+struct MediaCollector {
+    media_dir: PathBuf,
+    staging_dir: PathBuf,
+    copied: usize,
+}
+
+impl MediaCollector {
+    fn new(media_dir: PathBuf) -> Self {
+        let ts = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let staging_dir =
+            env::temp_dir().join(format!("dict-tui-anki-{}-{}", std::process::id(), ts));
+        Self {
+            media_dir,
+            staging_dir,
+            copied: 0,
+        }
+    }
+
+    /// 返回 HTML 中的引用到媒体文件名的映射。
+    fn collect(
+        &mut self,
+        raw_html: &str,
+        asset_dir: &Path,
+        source: &str,
+    ) -> Result<HashMap<String, String>> {
+        let _ = fs::remove_dir_all(&self.staging_dir);
+        let extracted = extract_referenced_resources(raw_html, asset_dir, &self.staging_dir)?;
+
+        let mut files = HashMap::new();
+        for (reference, path) in extracted {
+            let relative = path.strip_prefix(&self.staging_dir).unwrap_or(&path);
+            let name = media_file_name(source, relative);
+            let target = self.media_dir.join(&name);
+            if !target.exists() {
+                fs::copy(&path, &target)
+                    .with_context(|| format!("复制媒体文件失败: {}", target.display()))?;
+                self.copied += 1;
+            }
+            files.insert(reference, name);
+        }
+        Ok(files)
+    }
+
+    fn cleanup(&self) {
+        let _ = fs::remove_dir_all(&self.staging_dir);
+    }
+}
+
+/// Anki 的媒体目录没有子目录，用来源词典和资源路径拼出不冲突的文件名。
+fn media_file_name(source: &str, relative: &Path) -> String {
+    let path = relative
+        .components()
+        .map(|part| part.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("_");
+    format!("{source}_{path}")
+        .chars()
+        .map(|ch| {
+            if ch.is_alphanumeric() || matches!(ch, '.' | '-' | '_') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn is_audio(reference: &str) -> bool {
+    let path = strip_sound_scheme(reference)
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default();
+    Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            AUDIO_EXTENSIONS
+                .iter()
+                .any(|audio| ext.eq_ignore_ascii_case(audio))
+        })
+}
+
+fn cards_to_tsv(cards: &[Card]) -> String {
+    let mut tsv = String::from("#separator:tab\n#html:true\n#tags column:3\n");
+    for card in cards {
+        let tags = std::iter::once(EXPORT_TAG.to_string())
+            .chain(
+                card.tags
+                    .iter()
+                    .map(|tag| tag.replace(char::is_whitespace, "_")),
+            )
+            .collect::<Vec<_>>()
+            .join(" ");
+        tsv.push_str(&format!(
+            "{}\t{}\t{}\n",
+            single_line(&card.front),
+            single_line(&card.back),
+            tags
+        ));
+    }
+    tsv
+}
+
+/// TSV 的字段不能包含制表符和换行。
+fn single_line(html: &str) -> String {
+    html.replace("\r\n", " ").replace(['\t', '\n', '\r'], " ")
+}
+
+fn text_to_html(text: &str) -> String {
+    escape_html(text.trim()).replace('\n', "<br>")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_line_replaces_tabs_and_newlines() {
+        assert_eq!(single_line("a\tb\r\nc\nd\re"), "a b c d e");
+        assert_eq!(single_line("<b>run</b>"), "<b>run</b>");
+    }
+
+    #[test]
+    fn cards_to_tsv_writes_header_and_tags() {
+        let cards = [Card {
+            front: "run".to_string(),
+            back: "to move\tfast\n<br>".to_string(),
+            tags: vec!["Oxford Dict".to_string(), "ai".to_string()],
+        }];
+        let tsv = cards_to_tsv(&cards);
+        let lines: Vec<&str> = tsv.lines().collect();
+        assert_eq!(
+            lines[..3],
+            ["#separator:tab", "#html:true", "#tags column:3"]
+        );
+        assert_eq!(
+            lines[3],
+            format!("run\tto move fast <br>\t{EXPORT_TAG} Oxford_Dict ai")
+        );
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn audio_detection_uses_extension() {
+        assert!(is_audio("sound://voice/run.MP3?x=1"));
+        assert!(!is_audio("img/run.png"));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod anki;
+
+pub use anki::{export_history, export_notebook};
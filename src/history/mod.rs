@@ -0,0 +1,5 @@
+pub mod stats;
+pub mod store;
+
+pub use stats::{HistorySort, WordStats};
+pub use store::{LookupHistory, LookupKind};
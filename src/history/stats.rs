@@ -0,0 +1,88 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::history::store::{LookupHistory, LookupKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistorySort {
+    Recent,
+    Frequency,
+}
+
+impl HistorySort {
+    pub fn toggle(self) -> Self {
+        match self {
+            HistorySort::Recent => HistorySort::Frequency,
+            HistorySort::Frequency => HistorySort::Recent,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HistorySort::Recent => "按最近查询",
+            HistorySort::Frequency => "最常查询的单词",
+        }
+    }
+}
+
+/// 同一个词 (不区分大小写) 的查询汇总。
+#[derive(Debug, Clone)]
+pub struct WordStats {
+    pub word: String,
+    /// 最近一次有来源词典的查询所用的词典。
+    pub source: String,
+    pub count: usize,
+    pub kind_counts: [usize; LookupKind::ALL.len()],
+    pub first_at: u64,
+    pub last_at: u64,
+}
+
+impl WordStats {
+    pub fn kind_count(&self, kind: LookupKind) -> usize {
+        self.kind_counts[kind.index()]
+    }
+}
+
+impl LookupHistory {
+    /// 汇总每个词的查询次数，`filter` 非空时只保留包含它的词。
+    pub fn word_stats(&self, sort: HistorySort, filter: &str) -> Vec<WordStats> {
+        let filter = filter.trim().to_lowercase();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        let mut stats: Vec<WordStats> = Vec::new();
+
+        for record in self.records() {
+            let key = record.word.to_lowercase();
+            if !filter.is_empty() && !key.contains(&filter) {
+                continue;
+            }
+            let pos = *positions.entry(key).or_insert_with(|| {
+                stats.push(WordStats {
+                    word: record.word.clone(),
+                    source: String::new(),
+                    count: 0,
+                    kind_counts: [0; LookupKind::ALL.len()],
+                    first_at: record.at,
+                    last_at: record.at,
+                });
+                stats.len() - 1
+            });
+            let item = &mut stats[pos];
+            item.count += 1;
+            item.kind_counts[record.kind.index()] += 1;
+            item.last_at = item.last_at.max(record.at);
+            if !record.source.is_empty() {
+                item.source = record.source.clone();
+            }
+        }
+
+        match sort {
+            HistorySort::Recent => stats.sort_by_key(|item| Reverse(item.last_at)),
+            HistorySort::Frequency => stats.sort_by(|a, b| {
+                b.count
+                    .cmp(&a.count)
+                    .then_with(|| b.last_at.cmp(&a.last_at))
+            }),
+        }
+        stats
+    }
+}
@@ -0,0 +1,101 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::app::storage::{load_data_json, save_data_json};
+use crate::app::time::now_secs;
+
+const HISTORY_FILE: &str = "history.json";
+/// 超出后丢弃最早的记录。
+const MAX_RECORDS: usize = 20_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LookupKind {
+    /// 在详情栏停留超过设定的秒数
+    View,
+    Browser,
+    Ai,
+}
+
+impl LookupKind {
+    pub const ALL: [LookupKind; 3] = [LookupKind::View, LookupKind::Browser, LookupKind::Ai];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LookupKind::View => "查看",
+            LookupKind::Browser => "网页预览",
+            LookupKind::Ai => "AI 查询",
+        }
+    }
+
+    pub(crate) fn index(self) -> usize {
+        match self {
+            LookupKind::View => 0,
+            LookupKind::Browser => 1,
+            LookupKind::Ai => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LookupRecord {
+    pub word: String,
+    /// 来源词典，AI 查询句子等没有对应词条时为空。
+    #[serde(default)]
+    pub source: String,
+    pub kind: LookupKind,
+    pub at: u64,
+}
+
+/// 查询历史，按时间顺序保存在数据目录 `history.json`。
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LookupHistory {
+    records: Vec<LookupRecord>,
+}
+
+impl LookupHistory {
+    pub fn load() -> Result<Self> {
+        load_data_json(HISTORY_FILE)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        save_data_json(HISTORY_FILE, self)
+    }
+
+    pub fn records(&self) -> &[LookupRecord] {
+        &self.records
+    }
+
+    pub fn record(&mut self, word: &str, source: &str, kind: LookupKind) {
+        let word = word.trim();
+        if word.is_empty() {
+            return;
+        }
+        self.records.push(LookupRecord {
+            word: word.to_string(),
+            source: source.to_string(),
+            kind,
+            at: now_secs(),
+        });
+        if self.records.len() > MAX_RECORDS {
+            let overflow = self.records.len() - MAX_RECORDS;
+            self.records.drain(..overflow);
+        }
+    }
+
+    /// 删除某个词 (不区分大小写) 的全部记录，返回删除的条数。
+    pub fn remove_word(&mut self, word: &str) -> usize {
+        let before = self.records.len();
+        self.records
+            .retain(|record| !record.word.eq_ignore_ascii_case(word));
+        before - self.records.len()
+    }
+
+    pub fn count_since(&self, since: u64) -> usize {
+        self.records
+            .iter()
+            .rev()
+            .take_while(|record| record.at >= since)
+            .count()
+    }
+}
@@ -4,6 +4,9 @@ mod dict;
 mod ai;
 mod ui;
 mod render;
+mod notebook;
+mod export;
+mod history;
 
 use std::io::{self, Write};
 use std::path::PathBuf;
@@ -17,7 +20,7 @@ use crate::ui::run_search;
 fn run() -> Result<()> {
     let config = Config::load().unwrap_or_default();
     println!("正在扫描并缓存词典...");
-    let mut store = load_store_with_prompt()?;
+    let store = load_store_with_prompt()?;
     println!(
         "缓存完成，共加载 {} 条词条，来自 {} 本词典。",
         store.entries.len(),
@@ -26,7 +29,7 @@ fn run() -> Result<()> {
     println!("进入动态搜索：输入/删除字符会实时查询。");
     println!("按 Esc 退出程序。");
 
-    run_search(&mut store, config)
+    run_search(&store, config)
 }
 
 fn load_store_with_prompt() -> Result<DictionaryStore> {
@@ -0,0 +1,5 @@
+pub mod review;
+pub mod store;
+
+pub use review::Grade;
+pub use store::{Notebook, NotebookEntry};
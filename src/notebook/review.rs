@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+const DEFAULT_EASE: f64 = 2.5;
+const MIN_EASE: f64 = 1.3;
+
+/// 复习时的自评，对应 SM-2 的回忆质量 0-5。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grade {
+    Again,
+    Hard,
+    Good,
+    Easy,
+}
+
+impl Grade {
+    pub const ALL: [Grade; 4] = [Grade::Again, Grade::Hard, Grade::Good, Grade::Easy];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Grade::Again => "忘记",
+            Grade::Hard => "困难",
+            Grade::Good => "良好",
+            Grade::Easy => "简单",
+        }
+    }
+
+    fn quality(self) -> u8 {
+        match self {
+            Grade::Again => 1,
+            Grade::Hard => 3,
+            Grade::Good => 4,
+            Grade::Easy => 5,
+        }
+    }
+}
+
+/// 单词的 SM-2 复习进度；新加入的单词立即到期。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewState {
+    pub repetitions: u32,
+    pub interval_days: u32,
+    pub ease: f64,
+    pub due_at: u64,
+    #[serde(default)]
+    pub lapses: u32,
+    #[serde(default)]
+    pub last_reviewed_at: Option<u64>,
+}
+
+impl Default for ReviewState {
+    fn default() -> Self {
+        Self {
+            repetitions: 0,
+            interval_days: 0,
+            ease: DEFAULT_EASE,
+            due_at: 0,
+            lapses: 0,
+            last_reviewed_at: None,
+        }
+    }
+}
+
+impl ReviewState {
+    pub fn is_due(&self, before: u64) -> bool {
+        self.due_at <= before
+    }
+
+    /// 按 SM-2 更新间隔和难度系数。
+    pub fn schedule(&mut self, grade: Grade, now: u64) {
+        let quality = grade.quality();
+        if quality >= 3 {
+            self.interval_days = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => ((self.interval_days as f64) * self.ease).round().max(1.0) as u32,
+            };
+            self.repetitions += 1;
+        } else {
+            self.repetitions = 0;
+            self.interval_days = 1;
+            self.lapses += 1;
+        }
+
+        let miss = f64::from(5 - quality);
+        self.ease = (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MIN_EASE);
+        self.due_at = now + u64::from(self.interval_days) * SECS_PER_DAY;
+        self.last_reviewed_at = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    #[test]
+    fn new_word_is_due_immediately() {
+        assert!(ReviewState::default().is_due(0));
+    }
+
+    #[test]
+    fn good_answers_grow_interval() {
+        let mut state = ReviewState::default();
+        let intervals: Vec<u32> = (0..4)
+            .map(|_| {
+                state.schedule(Grade::Good, NOW);
+                state.interval_days
+            })
+            .collect();
+        assert_eq!(intervals, [1, 6, 15, 38]);
+        assert_eq!(state.repetitions, 4);
+        assert!((state.ease - DEFAULT_EASE).abs() < 1e-9);
+        assert_eq!(state.due_at, NOW + 38 * SECS_PER_DAY);
+        assert_eq!(state.last_reviewed_at, Some(NOW));
+        assert!(!state.is_due(NOW));
+    }
+
+    #[test]
+    fn easy_and_hard_adjust_ease() {
+        let mut easy = ReviewState::default();
+        easy.schedule(Grade::Easy, NOW);
+        assert!((easy.ease - 2.6).abs() < 1e-9);
+
+        let mut hard = ReviewState::default();
+        hard.schedule(Grade::Hard, NOW);
+        assert!((hard.ease - 2.36).abs() < 1e-9);
+        assert_eq!(hard.interval_days, 1);
+    }
+
+    #[test]
+    fn again_resets_progress_and_counts_lapse() {
+        let mut state = ReviewState::default();
+        state.schedule(Grade::Good, NOW);
+        state.schedule(Grade::Good, NOW);
+        state.schedule(Grade::Again, NOW);
+        assert_eq!(state.repetitions, 0);
+        assert_eq!(state.interval_days, 1);
+        assert_eq!(state.lapses, 1);
+        assert_eq!(state.due_at, NOW + SECS_PER_DAY);
+    }
+
+    #[test]
+    fn ease_never_drops_below_minimum() {
+        let mut state = ReviewState::default();
+        for _ in 0..10 {
+            state.schedule(Grade::Again, NOW);
+        }
+        assert!((state.ease - MIN_EASE).abs() < 1e-9);
+        assert_eq!(state.lapses, 10);
+    }
+}
@@ -0,0 +1,178 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::app::storage::{load_data_json, save_data_json};
+use crate::app::time::now_secs;
+use crate::notebook::review::ReviewState;
+
+const NOTEBOOK_FILE: &str = "notebook.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotebookEntry {
+    pub word: String,
+    pub source: String,
+    /// 保存时词条释义的纯文本。
+    pub definition: String,
+    #[serde(default)]
+    pub ai_answer: Option<String>,
+    #[serde(default)]
+    pub note: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub created_at: u64,
+    pub updated_at: u64,
+    #[serde(default)]
+    pub review: ReviewState,
+}
+
+impl NotebookEntry {
+    pub fn new(
+        word: String,
+        source: String,
+        definition: String,
+        ai_answer: Option<String>,
+    ) -> Self {
+        let now = now_secs();
+        Self {
+            word,
+            source,
+            definition,
+            ai_answer,
+            note: String::new(),
+            tags: Vec::new(),
+            created_at: now,
+            updated_at: now,
+            review: ReviewState::default(),
+        }
+    }
+
+    fn matches(&self, text_terms: &[String], tag_terms: &[String]) -> bool {
+        let tags: Vec<String> = self.tags.iter().map(|tag| tag.to_lowercase()).collect();
+        let haystack = format!(
+            "{}\n{}\n{}",
+            self.word.to_lowercase(),
+            self.note.to_lowercase(),
+            self.definition.to_lowercase()
+        );
+        tag_terms
+            .iter()
+            .all(|term| tags.iter().any(|tag| tag.contains(term.as_str())))
+            && text_terms
+                .iter()
+                .all(|term| haystack.contains(term.as_str()))
+    }
+}
+
+/// 生词本，保存在数据目录 `notebook.json`。
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Notebook {
+    entries: Vec<NotebookEntry>,
+    /// 读取失败后使用的空生词本，不允许保存，以免覆盖原文件
+    #[serde(skip)]
+    load_failed: bool,
+}
+
+impl Notebook {
+    pub fn load() -> Result<Self> {
+        load_data_json(NOTEBOOK_FILE)
+    }
+
+    /// 读取失败时代替使用的空生词本，本次运行中的改动不会写入磁盘。
+    pub fn unsaved() -> Self {
+        Self {
+            entries: Vec::new(),
+            load_failed: true,
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if self.load_failed {
+            bail!("启动时读取生词本失败，为避免覆盖原有内容不会保存");
+        }
+        save_data_json(NOTEBOOK_FILE, self)
+    }
+
+    pub fn entries(&self) -> &[NotebookEntry] {
+        &self.entries
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut NotebookEntry> {
+        self.entries.get_mut(index)
+    }
+
+    /// 同一词典的同一个词只保存一次：已存在时更新释义和 AI 回答，保留备注和标签。
+    /// 返回是否为新加入的词。
+    pub fn upsert(&mut self, entry: NotebookEntry) -> bool {
+        let existing = self.entries.iter_mut().find(|existing| {
+            existing.word.eq_ignore_ascii_case(&entry.word) && existing.source == entry.source
+        });
+        match existing {
+            Some(existing) => {
+                existing.definition = entry.definition;
+                if entry.ai_answer.is_some() {
+                    existing.ai_answer = entry.ai_answer;
+                }
+                existing.updated_at = entry.updated_at;
+                false
+            }
+            None => {
+                self.entries.push(entry);
+                true
+            }
+        }
+    }
+
+    /// 在 `before` 之前到期的单词索引，最早到期的排在前面。
+    pub fn due_indexes(&self, before: u64) -> Vec<usize> {
+        let mut indexes: Vec<usize> = (0..self.entries.len())
+            .filter(|&idx| self.entries[idx].review.is_due(before))
+            .collect();
+        indexes.sort_by_key(|&idx| self.entries[idx].review.due_at);
+        indexes
+    }
+
+    pub fn due_count(&self, before: u64) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.review.is_due(before))
+            .count()
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<NotebookEntry> {
+        (index < self.entries.len()).then(|| self.entries.remove(index))
+    }
+
+    /// 按添加时间倒序返回匹配的索引。过滤词以空格分隔，`#` 开头的词匹配标签，
+    /// 其余匹配单词、备注和释义。
+    pub fn search(&self, filter: &str) -> Vec<usize> {
+        let mut text_terms = Vec::new();
+        let mut tag_terms = Vec::new();
+        for term in filter.split_whitespace() {
+            match term.strip_prefix('#') {
+                Some(tag) if !tag.is_empty() => tag_terms.push(tag.to_lowercase()),
+                Some(_) => {}
+                None => text_terms.push(term.to_lowercase()),
+            }
+        }
+
+        self.entries
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, entry)| entry.matches(&text_terms, &tag_terms))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+}
+
+/// 解析以逗号或空白分隔的标签，去掉 `#` 前缀并去重。
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(|ch: char| ch == ',' || ch == '，' || ch.is_whitespace()) {
+        let tag = tag.trim().trim_start_matches('#');
+        if !tag.is_empty() && !tags.iter().any(|existing| existing == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
@@ -0,0 +1,86 @@
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+
+use crate::ai::structured::{parse_structured_answer, StructuredAnswer};
+use crate::render::markdown::markdown_to_lines;
+use crate::ui::theme::Theme;
+
+/// 把 AI 回答正文渲染为终端行：能解析为结构化 JSON 时按固定分节显示，否则按 Markdown 渲染。
+pub fn render_answer_body(answer: &str, theme: &Theme) -> Vec<Line<'static>> {
+    match parse_structured_answer(answer) {
+        Some(structured) => structured_answer_lines(&structured, theme),
+        None => markdown_to_lines(answer, theme),
+    }
+}
+
+pub fn text_to_plain(text: &Text) -> String {
+    text.lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn structured_answer_lines(answer: &StructuredAnswer, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+
+    push_section(&mut lines, "核心含义", theme);
+    lines.push(Line::from(answer.core_meaning.trim().to_string()));
+
+    if !answer.senses.is_empty() {
+        push_section(&mut lines, "释义", theme);
+        for (idx, sense) in answer.senses.iter().enumerate() {
+            let mut spans = vec![Span::raw(format!("{}. ", idx + 1))];
+            if !sense.part_of_speech.trim().is_empty() {
+                spans.push(Span::styled(
+                    format!("{} ", sense.part_of_speech.trim()),
+                    theme.accent,
+                ));
+            }
+            spans.push(Span::raw(sense.meaning.trim().to_string()));
+            lines.push(Line::from(spans));
+        }
+    }
+
+    if !answer.examples.is_empty() {
+        push_section(&mut lines, "例句", theme);
+        for example in &answer.examples {
+            lines.push(Line::styled(
+                format!("• {}", example.sentence.trim()),
+                Style::default().add_modifier(Modifier::ITALIC),
+            ));
+            if !example.translation.trim().is_empty() {
+                lines.push(Line::styled(
+                    format!("  {}", example.translation.trim()),
+                    Style::default().add_modifier(Modifier::DIM),
+                ));
+            }
+        }
+    }
+
+    push_list_section(&mut lines, "常见搭配", &answer.collocations, theme);
+    push_list_section(&mut lines, "来源", &answer.sources, theme);
+    lines
+}
+
+fn push_section(lines: &mut Vec<Line<'static>>, title: &str, theme: &Theme) {
+    if !lines.is_empty() {
+        lines.push(Line::default());
+    }
+    lines.push(Line::styled(title.to_string(), theme.headword));
+}
+
+fn push_list_section(lines: &mut Vec<Line<'static>>, title: &str, items: &[String], theme: &Theme) {
+    if items.is_empty() {
+        return;
+    }
+    push_section(lines, title, theme);
+    for item in items {
+        lines.push(Line::from(format!("• {}", item.trim())));
+    }
+}
@@ -33,7 +33,7 @@ pub fn build_preview_html_file(
     let body_html = if allow_javascript {
         raw_html.to_string()
     } else {
-        SCRIPT_RE.replace_all(&raw_html, "").to_string()
+        strip_scripts(&raw_html)
     };
     let css_links = collect_css_links(&asset_dir)?;
     extra_head.insert_str(0, &css_links);
@@ -97,6 +97,49 @@ pub fn open_in_browser(path: &Path) -> Result<()> {
     Err(anyhow!("当前平台不支持自动打开浏览器"))
 }
 
+pub fn strip_scripts(raw_html: &str) -> String {
+    SCRIPT_RE.replace_all(raw_html, "").to_string()
+}
+
+/// 从资源目录下的 MDD 资源包中提取 HTML 引用的图片、音频等资源，
+/// 返回 HTML 中的引用到提取后文件的映射。
+pub fn extract_referenced_resources(
+    raw_html: &str,
+    asset_dir: &Path,
+    output_dir: &Path,
+) -> Result<HashMap<String, PathBuf>> {
+    let mut extracted = HashMap::new();
+    let references = referenced_resources(raw_html);
+    if references.is_empty() {
+        return Ok(extracted);
+    }
+
+    for mdd_file in collect_mdd_files(asset_dir)? {
+        let mut mdd = Mdd::new(&mdd_file)
+            .with_context(|| format!("打开 MDD 资源包失败: {}", mdd_file.display()))?;
+        extract_references(&mut mdd, &references, output_dir, &mut extracted)?;
+    }
+    Ok(extracted)
+}
+
+/// 把 HTML 中 `src`/`href` 等属性引用的资源替换为新的地址。
+pub fn rewrite_resource_refs(raw_html: &str, replacements: &HashMap<String, String>) -> String {
+    if replacements.is_empty() {
+        return raw_html.to_string();
+    }
+
+    RESOURCE_ATTR_RE
+        .replace_all(raw_html, |caps: &regex::Captures| {
+            let full = caps.get(0).map(|m| m.as_str()).unwrap_or_default();
+            let value = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
+            let Some(replacement) = replacements.get(value.trim()) else {
+                return full.to_string();
+            };
+            full.replacen(value, replacement, 1)
+        })
+        .to_string()
+}
+
 fn collect_css_links(asset_dir: &Path) -> Result<String> {
     let mut css_files = Vec::new();
     for entry in fs::read_dir(asset_dir)
@@ -169,18 +212,33 @@ fn collect_mdd_resources(raw_html: &str, asset_dir: &Path, output_dir: &Path) ->
             }
         }
 
-        for reference in &references {
-            for candidate in resource_key_candidates(reference) {
-                if extract_mdd_resource(&mut mdd, &candidate, output_dir)?.is_some() {
-                    break;
-                }
-            }
-        }
+        extract_references(&mut mdd, &references, output_dir, &mut HashMap::new())?;
     }
 
     Ok(head)
 }
 
+/// 已在前面的资源包中找到的引用不再重复提取。
+fn extract_references(
+    mdd: &mut Mdd,
+    references: &[String],
+    output_dir: &Path,
+    extracted: &mut HashMap<String, PathBuf>,
+) -> Result<()> {
+    for reference in references {
+        if extracted.contains_key(reference) {
+            continue;
+        }
+        for candidate in resource_key_candidates(reference) {
+            if let Some(path) = extract_mdd_resource(mdd, &candidate, output_dir)? {
+                extracted.insert(reference.clone(), path);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
 fn rewrite_mdd_resource_refs(
     raw_html: &str,
     asset_dir: &Path,
@@ -203,20 +261,7 @@ fn rewrite_mdd_resource_refs(
         }
     }
 
-    if replacements.is_empty() {
-        return Ok(raw_html.to_string());
-    }
-
-    Ok(RESOURCE_ATTR_RE
-        .replace_all(raw_html, |caps: &regex::Captures| {
-            let full = caps.get(0).map(|m| m.as_str()).unwrap_or_default();
-            let value = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
-            let Some(replacement) = replacements.get(value) else {
-                return full.to_string();
-            };
-            full.replacen(value, replacement, 1)
-        })
-        .to_string())
+    Ok(rewrite_resource_refs(raw_html, &replacements))
 }
 
 fn collect_mdd_files(asset_dir: &Path) -> Result<Vec<PathBuf>> {
@@ -261,7 +306,7 @@ fn is_local_resource_ref(value: &str) -> bool {
 }
 
 fn resource_key_candidates(reference: &str) -> Vec<String> {
-    let clean = reference
+    let clean = strip_sound_scheme(reference)
         .split(['?', '#'])
         .next()
         .unwrap_or(reference)
@@ -281,6 +326,15 @@ fn resource_key_candidates(reference: &str) -> Vec<String> {
     candidates
 }
 
+/// MDX 词典常用 `sound://` 链接指向 MDD 中的音频。
+pub fn strip_sound_scheme(reference: &str) -> &str {
+    let prefix_len = "sound://".len();
+    match reference.get(..prefix_len) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("sound://") => &reference[prefix_len..],
+        _ => reference,
+    }
+}
+
 fn extract_mdd_resource(mdd: &mut Mdd, key: &str, output_dir: &Path) -> Result<Option<PathBuf>> {
     let Some(bytes) = mdd.locate_raw(key) else {
         return Ok(None);
@@ -333,3 +387,23 @@ fn escape_html_text(text: &str) -> String {
         .replace('>', "&gt;")
         .replace('"', "&quot;")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_sound_scheme_is_case_insensitive() {
+        assert_eq!(strip_sound_scheme("sound://a/b.mp3"), "a/b.mp3");
+        assert_eq!(strip_sound_scheme("SOUND://b.spx"), "b.spx");
+        assert_eq!(strip_sound_scheme("img/c.png"), "img/c.png");
+        assert_eq!(strip_sound_scheme("snd"), "snd");
+    }
+
+    #[test]
+    fn resource_keys_cover_both_separators() {
+        let candidates = resource_key_candidates("sound://./voice/run.mp3?t=1");
+        assert!(candidates.contains(&"voice/run.mp3".to_string()));
+        assert!(candidates.contains(&"\\voice\\run.mp3".to_string()));
+    }
+}
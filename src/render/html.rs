@@ -40,6 +40,18 @@ static HEX_ENTITY_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"&#x([0-9a-fA-F]{1,6});").expect("valid hex entity regex"));
 static CONTROL_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"[\x00-\x08\x0b\x0c\x0e-\x1f\x7f]").expect("valid control regex"));
+static CROSS_REF_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)<a\b[^>]*\bhref\s*=\s*["'](?:entry|bword)://([^"'#]*)[^"']*["'][^>]*>(.*?)</a>"#)
+        .expect("valid cross reference regex")
+});
+
+/// 词条中指向其他词条的链接 (`entry://` 或 `bword://`)。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrossRef {
+    /// 链接在纯文本中显示的文字
+    pub text: String,
+    pub target: String,
+}
 
 pub fn html_to_plain_text(raw_html: &str) -> String {
     let clean_input = CONTROL_RE.replace_all(raw_html, "");
@@ -71,6 +83,30 @@ pub fn html_to_plain_text(raw_html: &str) -> String {
         .to_string()
 }
 
+/// 按出现顺序提取交叉引用，相同的链接只保留一次。
+pub fn cross_references(raw_html: &str) -> Vec<CrossRef> {
+    let mut links: Vec<CrossRef> = Vec::new();
+    for caps in CROSS_REF_RE.captures_iter(raw_html) {
+        let target = decode_basic_entities(&caps[1]).trim().to_string();
+        if target.is_empty() {
+            continue;
+        }
+        let inner = TAG_RE.replace_all(&caps[2], " ");
+        let text = decode_basic_entities(&inner)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let link = CrossRef {
+            text: if text.is_empty() { target.clone() } else { text },
+            target,
+        };
+        if !links.contains(&link) {
+            links.push(link);
+        }
+    }
+    links
+}
+
 fn dedupe_adjacent_lines(lines: &[String]) -> Vec<String> {
     let mut output = Vec::with_capacity(lines.len());
     let mut last = "";
@@ -0,0 +1,285 @@
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::ui::theme::Theme;
+
+/// 把 Markdown 渲染为终端行：标题、强调、列表、引用、代码和链接转为对应样式。
+///
+/// 软换行按原文换行处理，避免中文段落被拼接出多余空格。标题、列表符号、代码和链接使用主题样式。
+pub fn markdown_to_lines(markdown: &str, theme: &Theme) -> Vec<Line<'static>> {
+    let mut renderer = MarkdownRenderer {
+        theme: theme.clone(),
+        ..MarkdownRenderer::default()
+    };
+    let options =
+        Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS;
+    for event in Parser::new_ext(markdown, options) {
+        renderer.handle(event);
+    }
+    renderer.finish()
+}
+
+#[derive(Default)]
+struct MarkdownRenderer {
+    lines: Vec<Line<'static>>,
+    spans: Vec<Span<'static>>,
+    styles: Vec<Style>,
+    /// 每层列表的下一个序号，无序列表为 `None`。
+    lists: Vec<Option<u64>>,
+    item_marker: Option<String>,
+    quote_depth: usize,
+    in_code_block: bool,
+    /// 链接地址及链接文字在 `spans` 中的起始位置。
+    link: Option<(String, usize)>,
+    table_cell: usize,
+    theme: Theme,
+}
+
+impl MarkdownRenderer {
+    fn handle(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start_tag(tag),
+            Event::End(tag) => self.end_tag(tag),
+            Event::Text(text) => self.push_text(&text),
+            Event::Code(code) => self.push_span(code.to_string(), self.theme.accent),
+            Event::InlineMath(math) | Event::DisplayMath(math) => {
+                self.push_span(math.to_string(), self.theme.accent)
+            }
+            Event::Html(html) | Event::InlineHtml(html) => self.push_text(&html),
+            Event::FootnoteReference(name) => self.push_span(
+                format!("[^{name}]"),
+                Style::default().add_modifier(Modifier::DIM),
+            ),
+            Event::SoftBreak | Event::HardBreak => self.flush_line(),
+            Event::Rule => {
+                self.start_block();
+                self.lines.push(Line::styled(
+                    "─".repeat(24),
+                    Style::default().add_modifier(Modifier::DIM),
+                ));
+            }
+            Event::TaskListMarker(checked) => {
+                let marker = if checked { "[x] " } else { "[ ] " };
+                self.push_span(marker.to_string(), self.theme.accent);
+            }
+        }
+    }
+
+    fn start_tag(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.start_block(),
+            Tag::Heading { level, .. } => {
+                self.start_block();
+                let mut style = self.theme.headword;
+                if level == HeadingLevel::H1 {
+                    style = style.add_modifier(Modifier::UNDERLINED);
+                }
+                self.styles.push(style);
+            }
+            Tag::BlockQuote(_) => {
+                self.start_block();
+                self.quote_depth += 1;
+                self.styles
+                    .push(Style::default().add_modifier(Modifier::ITALIC));
+            }
+            Tag::CodeBlock(kind) => {
+                self.start_block();
+                self.in_code_block = true;
+                if let CodeBlockKind::Fenced(lang) = kind {
+                    if !lang.trim().is_empty() {
+                        self.push_prefixed_line(vec![Span::styled(
+                            lang.trim().to_string(),
+                            Style::default().add_modifier(Modifier::DIM),
+                        )]);
+                    }
+                }
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.start_block();
+                } else {
+                    self.flush_line();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush_line();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        let marker = format!("{number}. ");
+                        *number += 1;
+                        marker
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.item_marker = Some(format!("{indent}{marker}"));
+            }
+            Tag::Emphasis => self
+                .styles
+                .push(Style::default().add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self
+                .styles
+                .push(Style::default().add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => self
+                .styles
+                .push(Style::default().add_modifier(Modifier::CROSSED_OUT)),
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.styles.push(self.theme.link);
+                self.link = Some((dest_url.to_string(), self.spans.len()));
+            }
+            Tag::Table(_) => self.start_block(),
+            Tag::TableHead => {
+                self.flush_line();
+                self.table_cell = 0;
+                self.styles
+                    .push(Style::default().add_modifier(Modifier::BOLD));
+            }
+            Tag::TableRow => {
+                self.flush_line();
+                self.table_cell = 0;
+            }
+            Tag::TableCell => {
+                if self.table_cell > 0 {
+                    self.spans.push(Span::styled(
+                        " │ ",
+                        Style::default().add_modifier(Modifier::DIM),
+                    ));
+                }
+                self.table_cell += 1;
+            }
+            _ => {}
+        }
+    }
+
+    fn end_tag(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::TableRow => self.flush_line(),
+            TagEnd::Heading(_) => {
+                self.flush_line();
+                self.styles.pop();
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush_line();
+                self.styles.pop();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+            }
+            TagEnd::CodeBlock => self.in_code_block = false,
+            TagEnd::List(_) => {
+                self.flush_line();
+                self.lists.pop();
+            }
+            TagEnd::Item => {
+                self.flush_line();
+                self.item_marker = None;
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            TagEnd::Link | TagEnd::Image => {
+                self.styles.pop();
+                if let Some((url, start)) = self.link.take() {
+                    let label: String = self.spans[start.min(self.spans.len())..]
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect();
+                    if !url.is_empty() && label.trim() != url {
+                        self.spans.push(Span::styled(
+                            format!(" ({url})"),
+                            Style::default().add_modifier(Modifier::DIM),
+                        ));
+                    }
+                }
+            }
+            TagEnd::TableHead => {
+                self.flush_line();
+                self.styles.pop();
+                self.push_prefixed_line(vec![Span::styled(
+                    "─".repeat(24),
+                    Style::default().add_modifier(Modifier::DIM),
+                )]);
+            }
+            _ => {}
+        }
+    }
+
+    fn current_style(&self) -> Style {
+        self.styles
+            .iter()
+            .fold(Style::default(), |acc, style| acc.patch(*style))
+    }
+
+    fn push_span(&mut self, text: String, style: Style) {
+        let style = self.current_style().patch(style);
+        self.spans.push(Span::styled(text, style));
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if self.in_code_block {
+            for line in text.lines() {
+                let span = Span::styled(format!("  {line}"), self.theme.accent);
+                self.push_prefixed_line(vec![span]);
+            }
+            return;
+        }
+
+        let mut parts = text.split('\n');
+        if let Some(first) = parts.next() {
+            self.push_span(first.to_string(), Style::default());
+        }
+        for part in parts {
+            self.flush_line();
+            self.push_span(part.to_string(), Style::default());
+        }
+    }
+
+    /// 块级元素之间空一行；列表项内部的段落保持紧凑。
+    fn start_block(&mut self) {
+        self.flush_line();
+        let last_is_blank = self
+            .lines
+            .last()
+            .is_none_or(|line| line.spans.iter().all(|span| span.content.is_empty()));
+        if self.lists.is_empty() && !last_is_blank {
+            self.lines.push(Line::default());
+        }
+    }
+
+    fn flush_line(&mut self) {
+        if self.spans.is_empty() {
+            return;
+        }
+        let spans = std::mem::take(&mut self.spans);
+        self.push_prefixed_line(spans);
+    }
+
+    fn push_prefixed_line(&mut self, spans: Vec<Span<'static>>) {
+        let mut line = Vec::with_capacity(spans.len() + 2);
+        if self.quote_depth > 0 {
+            line.push(Span::styled(
+                "│ ".repeat(self.quote_depth),
+                Style::default().add_modifier(Modifier::DIM),
+            ));
+        }
+        if let Some(marker) = self.item_marker.take() {
+            line.push(Span::styled(marker, self.theme.accent));
+        } else if !self.lists.is_empty() {
+            line.push(Span::raw("  ".repeat(self.lists.len())));
+        }
+        line.extend(spans);
+        self.lines.push(Line::from(line));
+    }
+
+    fn finish(mut self) -> Vec<Line<'static>> {
+        self.flush_line();
+        while self
+            .lines
+            .last()
+            .is_some_and(|line| line.spans.iter().all(|span| span.content.is_empty()))
+        {
+            self.lines.pop();
+        }
+        self.lines
+    }
+}
@@ -1,5 +1,7 @@
 pub mod html;
 pub mod browser;
+pub mod ai_answer;
+pub mod markdown;
 
 pub use html::html_to_plain_text;
 pub use browser::build_preview_html_file;
@@ -0,0 +1,299 @@
+use std::io;
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{backend::CrosstermBackend, Frame, Terminal};
+
+use crate::app::time::format_timestamp;
+use crate::cache::{AiAnswerCache, AiCacheRecord};
+use crate::ui::help::{HelpOverlay, HelpSection};
+use crate::ui::keymap::{fixed_command, FixedKey};
+use crate::ui::theme::Theme;
+
+const PAGE_STEP: usize = 10;
+/// AI 缓存页的按键，按键处理和帮助窗口共用。
+const AI_HISTORY_KEYS: [FixedKey<AiHistoryCommand>; 11] = [
+    FixedKey {
+        keys: &["Enter"],
+        label: "在搜索页打开该回答",
+        command: AiHistoryCommand::Open,
+    },
+    FixedKey {
+        keys: &["Delete", "Ctrl+D"],
+        label: "删除该缓存",
+        command: AiHistoryCommand::Delete,
+    },
+    FixedKey {
+        keys: &["Up"],
+        label: "上一条",
+        command: AiHistoryCommand::Up,
+    },
+    FixedKey {
+        keys: &["Down"],
+        label: "下一条",
+        command: AiHistoryCommand::Down,
+    },
+    FixedKey {
+        keys: &["Home"],
+        label: "第一条",
+        command: AiHistoryCommand::First,
+    },
+    FixedKey {
+        keys: &["End"],
+        label: "最后一条",
+        command: AiHistoryCommand::Last,
+    },
+    FixedKey {
+        keys: &["PageUp"],
+        label: "向上滚动回答",
+        command: AiHistoryCommand::ScrollUp,
+    },
+    FixedKey {
+        keys: &["PageDown"],
+        label: "向下滚动回答",
+        command: AiHistoryCommand::ScrollDown,
+    },
+    FixedKey {
+        keys: &["Backspace"],
+        label: "删除过滤条件的最后一个字符",
+        command: AiHistoryCommand::DeleteFilterChar,
+    },
+    FixedKey {
+        keys: &["F1"],
+        label: "帮助",
+        command: AiHistoryCommand::Help,
+    },
+    FixedKey {
+        keys: &["Esc"],
+        label: "返回搜索",
+        command: AiHistoryCommand::Back,
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AiHistoryCommand {
+    Open,
+    Delete,
+    Up,
+    Down,
+    First,
+    Last,
+    ScrollUp,
+    ScrollDown,
+    DeleteFilterChar,
+    Help,
+    Back,
+}
+
+struct HistoryState {
+    filter: String,
+    matches: Vec<usize>,
+    selected: usize,
+    preview_scroll: usize,
+    status: String,
+    help: Option<HelpOverlay>,
+    theme: Theme,
+}
+
+impl HistoryState {
+    fn new(cache: &AiAnswerCache, theme: &Theme) -> Self {
+        let mut state = Self {
+            filter: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+            preview_scroll: 0,
+            status: "输入过滤 | ↑/↓ 选择 | Enter 打开 | Delete 删除 | PageUp/PageDown 滚动回答 | F1 帮助 | Esc 返回"
+                .to_string(),
+            help: None,
+            theme: theme.clone(),
+        };
+        state.refresh(cache);
+        state
+    }
+
+    fn refresh(&mut self, cache: &AiAnswerCache) {
+        self.matches = cache.search(&self.filter);
+        self.selected = self.selected.min(self.matches.len().saturating_sub(1));
+        self.preview_scroll = 0;
+    }
+
+    fn selected_record_index(&self) -> Option<usize> {
+        self.matches.get(self.selected).copied()
+    }
+
+    fn select(&mut self, selected: usize) {
+        self.selected = selected.min(self.matches.len().saturating_sub(1));
+        self.preview_scroll = 0;
+    }
+}
+
+/// 浏览已缓存的 AI 回答。返回用户选择打开的记录，Esc 返回 `None`。
+pub fn run_ai_history(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    cache: &mut AiAnswerCache,
+    theme: &Theme,
+) -> Result<Option<AiCacheRecord>> {
+    let mut state = HistoryState::new(cache, theme);
+
+    loop {
+        terminal.draw(|frame| draw_history_ui(frame, cache, &state))?;
+
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        if let Some(help) = state.help.as_mut() {
+            if help.handle_key(&key) {
+                state.help = None;
+            }
+            continue;
+        }
+
+        let Some(command) = fixed_command(&AI_HISTORY_KEYS, &key) else {
+            if let KeyCode::Char(ch) = key.code {
+                if !ch.is_control() && !key.modifiers.contains(KeyModifiers::CONTROL) {
+                    state.filter.push(ch);
+                    state.selected = 0;
+                    state.refresh(cache);
+                }
+            }
+            continue;
+        };
+        match command {
+            AiHistoryCommand::Back => return Ok(None),
+            AiHistoryCommand::Open => {
+                if let Some(idx) = state.selected_record_index() {
+                    return Ok(cache.records().get(idx).cloned());
+                }
+            }
+            AiHistoryCommand::Delete => delete_selected(&mut state, cache),
+            AiHistoryCommand::Up => state.select(state.selected.saturating_sub(1)),
+            AiHistoryCommand::Down => state.select(state.selected + 1),
+            AiHistoryCommand::First => state.select(0),
+            AiHistoryCommand::Last => state.select(state.matches.len().saturating_sub(1)),
+            AiHistoryCommand::ScrollUp => {
+                state.preview_scroll = state.preview_scroll.saturating_sub(PAGE_STEP);
+            }
+            AiHistoryCommand::ScrollDown => {
+                state.preview_scroll = state.preview_scroll.saturating_add(PAGE_STEP);
+            }
+            AiHistoryCommand::DeleteFilterChar => {
+                state.filter.pop();
+                state.selected = 0;
+                state.refresh(cache);
+            }
+            AiHistoryCommand::Help => {
+                state.help = Some(HelpOverlay::new(
+                    "AI 缓存页快捷键",
+                    vec![HelpSection::from_keys(
+                        "AI 缓存",
+                        &AI_HISTORY_KEYS,
+                        &[("字符", "输入过滤条件")],
+                    )],
+                ));
+            }
+        }
+    }
+}
+
+fn delete_selected(state: &mut HistoryState, cache: &mut AiAnswerCache) {
+    let Some(idx) = state.selected_record_index() else {
+        return;
+    };
+    let Some(record) = cache.remove(idx) else {
+        return;
+    };
+
+    state.status = match cache.save() {
+        Ok(()) => format!("已删除缓存: {}", record.query),
+        Err(err) => format!("删除后保存缓存失败: {err}"),
+    };
+    state.refresh(cache);
+}
+
+fn draw_history_ui(frame: &mut Frame, cache: &AiAnswerCache, state: &HistoryState) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ])
+        .split(frame.area());
+
+    let input = Paragraph::new(state.filter.as_str()).block(
+        Block::default()
+            .title(format!("AI 回答缓存 (共 {} 条)", cache.records().len()))
+            .borders(Borders::ALL),
+    );
+    frame.render_widget(input, rows[0]);
+    frame.render_widget(Paragraph::new(state.status.as_str()), rows[1]);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(rows[2]);
+
+    let list_items: Vec<ListItem> = if state.matches.is_empty() {
+        vec![ListItem::new("没有缓存的 AI 回答")]
+    } else {
+        state
+            .matches
+            .iter()
+            .map(|idx| {
+                let record = &cache.records()[*idx];
+                ListItem::new(format!(
+                    "{}  [{}] {} {}",
+                    record.query,
+                    record.template,
+                    record.key.model,
+                    format_timestamp(record.created_at)
+                ))
+            })
+            .collect()
+    };
+
+    let list = List::new(list_items)
+        .block(Block::default().title("已缓存查询").borders(Borders::ALL))
+        .highlight_style(state.theme.highlight)
+        .highlight_symbol(">> ");
+
+    let mut list_state = ListState::default();
+    if !state.matches.is_empty() {
+        list_state.select(Some(state.selected));
+    }
+    frame.render_stateful_widget(list, columns[0], &mut list_state);
+
+    let preview_text = state
+        .selected_record_index()
+        .and_then(|idx| cache.records().get(idx))
+        .map(|record| {
+            format!(
+                "{}\n缓存于 {} | 模型 {}\n\n{}",
+                record.context.trim(),
+                format_timestamp(record.created_at),
+                record.key.model,
+                record.answer
+            )
+        })
+        .unwrap_or_default();
+    let scroll = state.preview_scroll.min(u16::MAX as usize) as u16;
+    let preview = Paragraph::new(preview_text)
+        .block(Block::default().title("回答预览").borders(Borders::ALL))
+        .scroll((scroll, 0))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(preview, columns[1]);
+    if let Some(help) = &state.help {
+        help.draw(frame, &state.theme);
+    }
+}
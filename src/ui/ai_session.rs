@@ -0,0 +1,564 @@
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use anyhow::Result;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Text};
+
+use crate::ai::client::{AiAnswer, TokenUsage};
+use crate::ai::structured::parse_structured_answer;
+use crate::ai::usage::{estimate_cost, UsageLedger};
+use crate::ai::AiClient;
+use crate::app::time::format_timestamp;
+use crate::app::Config;
+use crate::cache::{AiAnswerCache, AiCacheKey, AiCacheRecord};
+use crate::render::ai_answer::{render_answer_body, text_to_plain};
+use crate::ui::keymap::{Action, Keymap};
+use crate::ui::theme::Theme;
+
+const AI_SPINNER: &[&str] = &["-", "\\", "|", "/"];
+const MAX_AI_SLOTS: usize = 20;
+
+type AiTaskResult = std::result::Result<AiAnswer, String>;
+
+/// 一次 AI 查询所需的全部输入，`user_prompt` 为已经渲染好的模板。
+pub struct AiRequest {
+    pub query: String,
+    pub context: String,
+    pub template: String,
+    pub user_prompt: String,
+}
+
+/// AI 会话对搜索界面的更新：只改状态栏，或同时替换详情内容。
+pub enum AiUpdate {
+    Status(String),
+    Detail { text: Text<'static>, status: String },
+}
+
+struct PendingAiQuery {
+    request: AiRequest,
+    key: AiCacheKey,
+    receiver: Receiver<AiTaskResult>,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+/// 每个查询词保留一个结果槽；查询进行中时 `pending` 非空，`detail` 为等待提示。
+struct AiSlot {
+    word: String,
+    query: String,
+    /// 发起查询的搜索标签页，完成提示和进度只显示在该标签页
+    tab: usize,
+    pending: Option<PendingAiQuery>,
+    detail: Text<'static>,
+    status: String,
+    /// 完成的回答原文，查询中或失败时为空
+    answer: Option<String>,
+}
+
+pub struct AiSession {
+    rt: tokio::runtime::Runtime,
+    slots: Vec<AiSlot>,
+    /// 详情栏当前显示的结果槽。
+    focus: Option<String>,
+    tick: usize,
+    cache: AiAnswerCache,
+    ledger: UsageLedger,
+    /// 渲染回答使用的主题，只影响之后完成或切换显示的回答
+    theme: Theme,
+    /// 提示文字中显示的按键
+    keymap: Keymap,
+}
+
+impl AiSession {
+    /// 读取缓存和用量记录失败时仍可使用，第二个返回值是要提示给用户的错误。
+    pub fn new() -> Result<(Self, Option<String>)> {
+        let rt = tokio::runtime::Runtime::new()?;
+        let mut warning = None;
+        let cache = AiAnswerCache::load().unwrap_or_else(|err| {
+            warning = Some(format!("读取 AI 缓存失败: {err}"));
+            AiAnswerCache::default()
+        });
+        let ledger = UsageLedger::load().unwrap_or_else(|err| {
+            warning = Some(format!("读取 AI 用量记录失败: {err}"));
+            UsageLedger::default()
+        });
+
+        Ok((
+            Self {
+                rt,
+                slots: Vec::new(),
+                focus: None,
+                tick: 0,
+                cache,
+                ledger,
+                theme: Theme::default(),
+                keymap: Keymap::default(),
+            },
+            warning,
+        ))
+    }
+
+    pub fn set_theme(&mut self, theme: &Theme) {
+        self.theme = theme.clone();
+    }
+
+    pub fn set_keymap(&mut self, keymap: &Keymap) {
+        self.keymap = keymap.clone();
+    }
+
+    pub fn cache(&self) -> &AiAnswerCache {
+        &self.cache
+    }
+
+    pub fn cache_mut(&mut self) -> &mut AiAnswerCache {
+        &mut self.cache
+    }
+
+    /// 为标签页 `tab` 开始查询并切换到该词的结果槽；同一个词已有进行中的查询时会先中止它。
+    pub fn start(
+        &mut self,
+        config: &Config,
+        request: AiRequest,
+        refresh: bool,
+        tab: usize,
+    ) -> AiUpdate {
+        let key = AiCacheKey::new(
+            &request.query,
+            &request.context,
+            &request.user_prompt,
+            config,
+        );
+        if !refresh {
+            if let Some(record) = self.cache.get(&key) {
+                let answer = record.answer.clone();
+                let update = cached_answer_update(record, &self.theme, &self.keymap);
+                return self.show(request.query, update, answer, tab);
+            }
+        }
+
+        let text = Text::from(build_ai_pending_detail(
+            &request.query,
+            &request.context,
+            &self.keymap,
+        ));
+        let status = format!("AI 查询 '{}' ({}) ...", request.query, request.template);
+        let query = request.query.clone();
+        let pending = self.spawn(config.clone(), request, key);
+        self.put_slot(
+            query,
+            tab,
+            Some(pending),
+            text.clone(),
+            status.clone(),
+            None,
+        );
+        AiUpdate::Detail { text, status }
+    }
+
+    pub fn show_cached(&mut self, record: &AiCacheRecord, tab: usize) -> AiUpdate {
+        self.show(
+            record.query.clone(),
+            cached_answer_update(record, &self.theme, &self.keymap),
+            record.answer.clone(),
+            tab,
+        )
+    }
+
+    /// 当前显示的 AI 回答渲染后的纯文本，用于复制。
+    pub fn focused_answer(&self) -> Option<String> {
+        let answer = self.slots[self.focus_index()?].answer.as_deref()?;
+        Some(text_to_plain(&Text::from(render_answer_body(
+            answer,
+            &self.theme,
+        ))))
+    }
+
+    /// 详情栏当前显示的结果槽，切换标签页时随标签页保存。
+    pub fn focus(&self) -> Option<String> {
+        self.focus.clone()
+    }
+
+    /// 切回标签页时恢复它的结果槽，返回该槽此刻的内容 (可能已在后台完成)；
+    /// 槽已被丢弃时返回 `None`。
+    pub fn restore_focus(&mut self, focus: Option<String>) -> Option<AiUpdate> {
+        self.focus = focus;
+        let slot = &self.slots[self.focus_index()?];
+        Some(AiUpdate::Detail {
+            text: slot.detail.clone(),
+            status: slot.status.clone(),
+        })
+    }
+
+    /// 取消当前显示的查询，没有时取消标签页 `tab` 最近开始的查询。
+    /// 返回被取消的查询词，以及它是否正显示在详情栏中。
+    pub fn cancel(&mut self, tab: usize) -> Option<(String, bool)> {
+        let idx = self
+            .focus_index()
+            .filter(|&idx| self.slots[idx].pending.is_some())
+            .or_else(|| {
+                self.slots
+                    .iter()
+                    .rposition(|slot| slot.tab == tab && slot.pending.is_some())
+            })?;
+        let slot = self.slots.remove(idx);
+        if let Some(pending) = slot.pending {
+            pending.handle.abort();
+        }
+        let was_focused = self.focus.as_deref() == Some(slot.word.as_str());
+        if was_focused {
+            self.focus = None;
+        }
+        Some((slot.query, was_focused))
+    }
+
+    /// 在已保留的结果之间循环切换。
+    pub fn switch(&mut self, delta: isize) -> Option<AiUpdate> {
+        if self.slots.is_empty() {
+            return None;
+        }
+        let len = self.slots.len();
+        let next = match self.focus_index() {
+            Some(idx) => (idx as isize + delta).rem_euclid(len as isize) as usize,
+            None if delta < 0 => len - 1,
+            None => 0,
+        };
+        let slot = &self.slots[next];
+        self.focus = Some(slot.word.clone());
+        Some(AiUpdate::Detail {
+            text: slot.detail.clone(),
+            status: format!("AI 结果 {}/{len}: {}", next + 1, slot.status),
+        })
+    }
+
+    /// 关闭标签页时中止它发起、尚未完成的查询；已完成的结果仍可切换查看。
+    pub fn close_tab(&mut self, tab: usize) {
+        self.slots.retain(|slot| match &slot.pending {
+            Some(pending) if slot.tab == tab => {
+                pending.handle.abort();
+                false
+            }
+            _ => true,
+        });
+    }
+
+    pub fn abort_all(&mut self) {
+        for slot in &mut self.slots {
+            if let Some(pending) = slot.pending.take() {
+                pending.handle.abort();
+            }
+        }
+    }
+
+    /// 收取完成的查询。返回标签页 `tab` 的更新：正在显示的结果完成时替换详情，
+    /// 该标签页在后台的查询完成时只提示状态；其他标签页的结果在切回时由 `restore_focus` 显示。
+    pub fn poll(&mut self, config: &Config, tab: usize) -> Option<AiUpdate> {
+        self.tick = self.tick.wrapping_add(1);
+        let mut focused_update = None;
+        let mut background_status = None;
+
+        for idx in 0..self.slots.len() {
+            let Some(pending) = &self.slots[idx].pending else {
+                continue;
+            };
+            let result = match pending.receiver.try_recv() {
+                Ok(result) => result,
+                Err(TryRecvError::Empty) => continue,
+                Err(TryRecvError::Disconnected) => Err("AI 任务已中断。".to_string()),
+            };
+            let Some(pending) = self.slots[idx].pending.take() else {
+                continue;
+            };
+
+            let (text, status, answer) = self.finish(config, pending, result);
+            let slot = &mut self.slots[idx];
+            slot.detail = text.clone();
+            slot.status = status.clone();
+            slot.answer = answer;
+            if self.focus.as_deref() == Some(slot.word.as_str()) {
+                focused_update = Some(AiUpdate::Detail { text, status });
+            } else if slot.tab == tab {
+                background_status =
+                    Some(format!("{status} ({} 切换查看)", switch_keys(&self.keymap)));
+            }
+        }
+
+        focused_update.or(background_status.map(AiUpdate::Status))
+    }
+
+    /// 标签页 `tab` 进行中的查询的进度，与状态栏文字分开显示。
+    pub fn pending_status(&self, tab: usize) -> Option<String> {
+        let pending_count = self
+            .slots
+            .iter()
+            .filter(|slot| slot.tab == tab && slot.pending.is_some())
+            .count();
+        if pending_count == 0 {
+            return None;
+        }
+
+        let spinner = AI_SPINNER[(self.tick / 2) % AI_SPINNER.len()];
+        let cancel = self.keymap.key_hint(Action::AiCancel);
+        match self
+            .focus_index()
+            .filter(|&idx| self.slots[idx].tab == tab && self.slots[idx].pending.is_some())
+        {
+            Some(idx) if pending_count > 1 => Some(format!(
+                "AI 查询中 {spinner} {} (另有 {} 个后台查询) | {cancel} 取消",
+                self.slots[idx].query,
+                pending_count - 1
+            )),
+            Some(idx) => Some(format!(
+                "AI 查询中 {spinner} {} | {cancel} 取消",
+                self.slots[idx].query
+            )),
+            None => Some(format!(
+                "AI 后台查询中 {spinner} {pending_count} 个 | {cancel} 取消"
+            )),
+        }
+    }
+
+    fn focus_index(&self) -> Option<usize> {
+        let focus = self.focus.as_deref()?;
+        self.slots.iter().position(|slot| slot.word == focus)
+    }
+
+    fn show(&mut self, query: String, update: AiUpdate, answer: String, tab: usize) -> AiUpdate {
+        if let AiUpdate::Detail { text, status } = &update {
+            self.put_slot(query, tab, None, text.clone(), status.clone(), Some(answer));
+        }
+        update
+    }
+
+    /// 替换同一个词的旧结果槽并切换过去；超出上限时优先丢弃最早的已完成结果。
+    fn put_slot(
+        &mut self,
+        query: String,
+        tab: usize,
+        pending: Option<PendingAiQuery>,
+        detail: Text<'static>,
+        status: String,
+        answer: Option<String>,
+    ) {
+        let word = query.trim().to_lowercase();
+        if let Some(idx) = self.slots.iter().position(|slot| slot.word == word) {
+            if let Some(old) = self.slots.remove(idx).pending {
+                old.handle.abort();
+            }
+        }
+        self.slots.push(AiSlot {
+            word: word.clone(),
+            query,
+            tab,
+            pending,
+            detail,
+            status,
+            answer,
+        });
+
+        while self.slots.len() > MAX_AI_SLOTS {
+            let idx = self
+                .slots
+                .iter()
+                .position(|slot| slot.pending.is_none())
+                .unwrap_or(0);
+            if let Some(old) = self.slots.remove(idx).pending {
+                old.handle.abort();
+            }
+        }
+        self.focus = Some(word);
+    }
+
+    fn spawn(&self, config: Config, request: AiRequest, key: AiCacheKey) -> PendingAiQuery {
+        let (sender, receiver) = mpsc::channel();
+        let user_prompt = request.user_prompt.clone();
+        let handle = self.rt.spawn(async move {
+            let result = match AiClient::new(config) {
+                Ok(ai) => ai.query(&user_prompt).await.map_err(|err| err.to_string()),
+                Err(err) => Err(err.to_string()),
+            };
+            let _ = sender.send(result);
+        });
+
+        PendingAiQuery {
+            request,
+            key,
+            receiver,
+            handle,
+        }
+    }
+
+    /// 查询失败时如有旧缓存则退回显示缓存。返回详情、状态和回答原文。
+    fn finish(
+        &mut self,
+        config: &Config,
+        pending: PendingAiQuery,
+        result: AiTaskResult,
+    ) -> (Text<'static>, String, Option<String>) {
+        let PendingAiQuery { request, key, .. } = pending;
+        match result {
+            Ok(answer) => {
+                let raw = answer.text.clone();
+                let (text, status) = self.store_answer(config, key, request, answer);
+                (text, status, Some(raw))
+            }
+            Err(err) => match self.cache.get(&key) {
+                Some(record) => {
+                    let note = format!(
+                        "刷新失败，显示 {} 的缓存",
+                        format_timestamp(record.created_at)
+                    );
+                    (
+                        build_ai_answer_detail(
+                            &record.query,
+                            &record.context,
+                            &record.answer,
+                            Some(&note),
+                            &self.theme,
+                        ),
+                        format!("AI 查询失败: {}: {err}", request.query),
+                        Some(record.answer.clone()),
+                    )
+                }
+                None => (
+                    Text::from(format!("--- AI 查询失败: {} ---\n\n{err}", request.query)),
+                    format!("AI 查询失败: {}", request.query),
+                    None,
+                ),
+            },
+        }
+    }
+
+    /// 缓存回答并记账。
+    fn store_answer(
+        &mut self,
+        config: &Config,
+        key: AiCacheKey,
+        request: AiRequest,
+        answer: AiAnswer,
+    ) -> (Text<'static>, String) {
+        let text = build_ai_answer_detail(
+            &request.query,
+            &request.context,
+            &answer.text,
+            None,
+            &self.theme,
+        );
+        let mut status = format!("AI 查询完成: {}", request.query);
+        if config.structured_answers && parse_structured_answer(&answer.text).is_none() {
+            status.push_str(" (结构化解析失败，按文本显示)");
+        }
+        if let Some(usage) = answer.usage {
+            let cost = estimate_cost(&usage, config.model_prices.get(&key.model));
+            self.ledger.record(&key.model, &usage, cost);
+            status.push_str(&format!(
+                " | {}",
+                format_usage_summary(&usage, cost, &self.ledger, &config.price_currency)
+            ));
+            if let Err(err) = self.ledger.save() {
+                status = format!("AI 查询完成，但写入用量记录失败: {err}");
+            }
+        }
+
+        self.cache.insert(AiCacheRecord::new(
+            key,
+            request.query,
+            request.context,
+            request.template,
+            answer.text,
+        ));
+        if let Err(err) = self.cache.save() {
+            status = format!("AI 查询完成，但写入缓存失败: {err}");
+        }
+        (text, status)
+    }
+}
+
+fn cached_answer_update(record: &AiCacheRecord, theme: &Theme, keymap: &Keymap) -> AiUpdate {
+    let note = format!(
+        "缓存于 {}，{} 刷新",
+        format_timestamp(record.created_at),
+        keymap.key_hint(Action::AiRefresh)
+    );
+    AiUpdate::Detail {
+        text: build_ai_answer_detail(
+            &record.query,
+            &record.context,
+            &record.answer,
+            Some(&note),
+            theme,
+        ),
+        status: format!("AI 缓存回答: {}", record.query),
+    }
+}
+
+fn format_usage_summary(
+    usage: &TokenUsage,
+    cost: Option<f64>,
+    ledger: &UsageLedger,
+    currency: &str,
+) -> String {
+    let session = ledger.session_totals();
+    let month = ledger.current_month_totals();
+    let cost_text = match cost {
+        Some(cost) => format!(" ≈ {currency}{cost:.4}"),
+        None => " (未知价格)".to_string(),
+    };
+    format!(
+        "tokens 输入 {} / 输出 {}{} | 本次会话 {} 次 {} | 本月 {}",
+        usage.prompt_tokens,
+        usage.completion_tokens,
+        cost_text,
+        session.requests,
+        session.cost_text(currency),
+        month.cost_text(currency)
+    )
+}
+
+/// 在多个 AI 结果之间切换的按键。
+fn switch_keys(keymap: &Keymap) -> String {
+    format!(
+        "{}/{}",
+        keymap.key_hint(Action::AiNext),
+        keymap.key_hint(Action::AiPrev)
+    )
+}
+
+fn build_ai_pending_detail(query: &str, context: &str, keymap: &Keymap) -> String {
+    let header = if context.trim().is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", context.trim())
+    };
+
+    format!(
+        "{}--- AI 回答: {} ---\n\nAI 查询中...\n\n可以继续输入、滚动或打开配置页；结果返回后会自动更新到这里。\n{} 取消查询，{} 在多个 AI 结果之间切换。",
+        header,
+        query,
+        keymap.key_hint(Action::AiCancel),
+        switch_keys(keymap)
+    )
+}
+
+fn build_ai_answer_detail(
+    query: &str,
+    context: &str,
+    answer: &str,
+    note: Option<&str>,
+    theme: &Theme,
+) -> Text<'static> {
+    let title = match note {
+        Some(note) => format!("{} ({})", query, note),
+        None => query.to_string(),
+    };
+
+    let mut lines = Vec::new();
+    if !context.trim().is_empty() {
+        lines.push(Line::from(context.trim().to_string()));
+    }
+    lines.push(Line::styled(
+        format!("--- AI 回答: {} ---", title),
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+    lines.push(Line::default());
+    lines.extend(render_answer_body(answer, theme));
+    Text::from(lines)
+}
@@ -0,0 +1,101 @@
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context, Result};
+
+/// 多数终端对 OSC 52 序列的长度限制约为 100KB (base64 编码后)。
+const OSC52_MAX_BYTES: usize = 74_994;
+const BASE64_TABLE: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// 复制文本到剪贴板，返回使用的方式。
+///
+/// 总是先发送 OSC 52 转义序列，由终端写入剪贴板，SSH 和 tmux 中同样有效；
+/// 配置了 `command` (如 `wl-copy`、`xclip -selection clipboard`) 时再把文本交给它，
+/// 供不支持 OSC 52 的本地终端使用。内容超出 OSC 52 的长度限制时只使用外部命令。
+pub fn copy_to_clipboard(text: &str, command: &str) -> Result<String> {
+    let command = command.trim();
+    let use_osc52 = text.len() <= OSC52_MAX_BYTES;
+    if !use_osc52 && command.is_empty() {
+        bail!(
+            "内容过长 ({} 字节)，超出 OSC 52 的限制，请在配置中设置剪贴板命令",
+            text.len()
+        );
+    }
+
+    if use_osc52 {
+        write_osc52(text).context("发送 OSC 52 失败")?;
+    }
+    if command.is_empty() {
+        return Ok("OSC 52".to_string());
+    }
+    run_command(command, text)?;
+    Ok(if use_osc52 {
+        format!("OSC 52 + {command}")
+    } else {
+        command.to_string()
+    })
+}
+
+fn write_osc52(text: &str) -> io::Result<()> {
+    let sequence = format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()));
+    // tmux 需要用 DCS 透传 (要求 `set -g allow-passthrough on`)
+    let sequence = if std::env::var_os("TMUX").is_some() {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    };
+    let mut stdout = io::stdout();
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()
+}
+
+/// 命令按空白切分后直接执行，不经过 shell。
+fn run_command(command: &str, text: &str) -> Result<()> {
+    let mut parts = command.split_whitespace();
+    let Some(program) = parts.next() else {
+        return Ok(());
+    };
+    let mut child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("无法运行剪贴板命令 {program}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .with_context(|| format!("写入剪贴板命令 {program} 失败"))?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        bail!("剪贴板命令 {program} 退出码 {status}");
+    }
+    Ok(())
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let indexes = [
+            b[0] >> 2,
+            ((b[0] & 0x03) << 4) | (b[1] >> 4),
+            ((b[1] & 0x0f) << 2) | (b[2] >> 6),
+            b[2] & 0x3f,
+        ];
+        for (idx, &value) in indexes.iter().enumerate() {
+            if idx <= chunk.len() {
+                output.push(BASE64_TABLE[value as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
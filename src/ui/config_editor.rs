@@ -1,19 +1,221 @@
+use std::collections::BTreeMap;
 use std::io;
 use std::time::Duration;
 
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use ratatui::layout::{Constraint, Direction, Layout, Position, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::Style;
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 use ratatui::{backend::CrosstermBackend, Frame, Terminal};
+use reqwest::header::{HeaderName, HeaderValue};
+
+use crate::ai::prompt::{parse_templates_text, templates_to_text};
+use crate::app::{Config, LayoutMode};
+use crate::ui::help::{HelpOverlay, HelpSection};
+use crate::ui::keymap::{fixed_command, FixedKey};
+use crate::ui::text_edit::{
+    backspace, delete_char, insert_char, move_left, move_right, single_line_view,
+};
+use crate::ui::theme::{Theme, THEME_NAMES};
+
+const FIELD_COUNT: usize = 25;
+const FIELD_API_ENDPOINT: usize = 0;
+const FIELD_API_KEY: usize = 1;
+const FIELD_API_KEY_ENV: usize = 2;
+const FIELD_API_KEY_FILE: usize = 3;
+const FIELD_MODEL: usize = 4;
+const FIELD_ANSWER_LANGUAGE: usize = 5;
+const FIELD_WEB_SEARCH: usize = 6;
+const FIELD_JAVASCRIPT: usize = 7;
+const FIELD_STRUCTURED: usize = 8;
+const FIELD_REQUEST_TIMEOUT: usize = 9;
+const FIELD_CONNECT_TIMEOUT: usize = 10;
+const FIELD_MAX_RETRIES: usize = 11;
+const FIELD_PROXY: usize = 12;
+const FIELD_NO_PROXY: usize = 13;
+const FIELD_CA_CERT: usize = 14;
+const FIELD_ANKI_HTML: usize = 15;
+const FIELD_HISTORY_DWELL: usize = 16;
+const FIELD_THEME: usize = 17;
+const FIELD_CLIPBOARD: usize = 18;
+const FIELD_LAYOUT_MODE: usize = 19;
+const FIELD_STACK_WIDTH: usize = 20;
+const FIELD_LIST_PERCENT: usize = 21;
+const FIELD_CUSTOM_HEADERS: usize = 22;
+const FIELD_SYSTEM_PROMPT: usize = 23;
+const FIELD_PROMPT_TEMPLATES: usize = 24;
+/// 从这里开始是多行字段，排在单行字段网格下方
+const FIRST_MULTILINE_FIELD: usize = FIELD_CUSTOM_HEADERS;
+const MASK_VISIBLE_CHARS: usize = 4;
+const MULTILINE_FIELD_COUNT: usize = FIELD_COUNT - FIRST_MULTILINE_FIELD;
+const GRID_COLUMNS: usize = 2;
+const FIELD_HEIGHT: u16 = 3;
+const MIN_PROMPT_HEIGHT: u16 = 8;
+/// 选择字段时的按键 (`handle_navigation_key`)。
+const NAVIGATION_KEYS: [FixedKey<NavCommand>; 10] = [
+    FixedKey {
+        keys: &["Up"],
+        label: "上一个字段",
+        command: NavCommand::Prev,
+    },
+    FixedKey {
+        keys: &["Down"],
+        label: "下一个字段",
+        command: NavCommand::Next,
+    },
+    FixedKey {
+        keys: &["Home"],
+        label: "第一个字段",
+        command: NavCommand::First,
+    },
+    FixedKey {
+        keys: &["End"],
+        label: "最后一个字段",
+        command: NavCommand::Last,
+    },
+    FixedKey {
+        keys: &["Enter"],
+        label: "编辑字段",
+        command: NavCommand::Edit,
+    },
+    FixedKey {
+        keys: &["PageUp"],
+        label: "向上滚动提示词",
+        command: NavCommand::ScrollUp,
+    },
+    FixedKey {
+        keys: &["PageDown"],
+        label: "向下滚动提示词",
+        command: NavCommand::ScrollDown,
+    },
+    FixedKey {
+        keys: &["Ctrl+S"],
+        label: "保存配置文件",
+        command: NavCommand::Save,
+    },
+    FixedKey {
+        keys: &["F1", "?"],
+        label: "帮助",
+        command: NavCommand::Help,
+    },
+    FixedKey {
+        keys: &["Esc"],
+        label: "返回搜索",
+        command: NavCommand::Back,
+    },
+];
+/// 编辑字段时的按键 (`handle_edit_key`)。
+const FIELD_EDIT_KEYS: [FixedKey<FieldEditCommand>; 15] = [
+    FixedKey {
+        keys: &["Enter"],
+        label: "完成 (提示词中为换行)",
+        command: FieldEditCommand::Enter,
+    },
+    FixedKey {
+        keys: &["F2"],
+        label: "完成编辑",
+        command: FieldEditCommand::Finish,
+    },
+    FixedKey {
+        keys: &["Tab"],
+        label: "完成并切换到下一个字段",
+        command: FieldEditCommand::NextField,
+    },
+    FixedKey {
+        keys: &["Shift+Tab"],
+        label: "完成并切换到上一个字段",
+        command: FieldEditCommand::PrevField,
+    },
+    FixedKey {
+        keys: &["Ctrl+S"],
+        label: "完成并保存配置文件",
+        command: FieldEditCommand::Save,
+    },
+    FixedKey {
+        keys: &["Esc"],
+        label: "取消编辑",
+        command: FieldEditCommand::Cancel,
+    },
+    FixedKey {
+        keys: &["Left"],
+        label: "光标左移",
+        command: FieldEditCommand::Left,
+    },
+    FixedKey {
+        keys: &["Right"],
+        label: "光标右移",
+        command: FieldEditCommand::Right,
+    },
+    FixedKey {
+        keys: &["Up"],
+        label: "提示词中光标上移一行",
+        command: FieldEditCommand::Up,
+    },
+    FixedKey {
+        keys: &["Down"],
+        label: "提示词中光标下移一行",
+        command: FieldEditCommand::Down,
+    },
+    FixedKey {
+        keys: &["Home"],
+        label: "移到行首",
+        command: FieldEditCommand::LineStart,
+    },
+    FixedKey {
+        keys: &["End"],
+        label: "移到行尾",
+        command: FieldEditCommand::LineEnd,
+    },
+    FixedKey {
+        keys: &["Backspace"],
+        label: "删除光标前的字符",
+        command: FieldEditCommand::Backspace,
+    },
+    FixedKey {
+        keys: &["Delete"],
+        label: "删除光标后的字符",
+        command: FieldEditCommand::Delete,
+    },
+    FixedKey {
+        keys: &["F1"],
+        label: "帮助",
+        command: FieldEditCommand::Help,
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NavCommand {
+    Prev,
+    Next,
+    First,
+    Last,
+    Edit,
+    ScrollUp,
+    ScrollDown,
+    Save,
+    Help,
+    Back,
+}
 
-use crate::app::Config;
-
-const FIELD_COUNT: usize = 7;
-const FIELD_WEB_SEARCH: usize = 4;
-const FIELD_JAVASCRIPT: usize = 5;
-const FIELD_SYSTEM_PROMPT: usize = 6;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldEditCommand {
+    Enter,
+    Finish,
+    NextField,
+    PrevField,
+    Save,
+    Cancel,
+    Left,
+    Right,
+    Up,
+    Down,
+    LineStart,
+    LineEnd,
+    Backspace,
+    Delete,
+    Help,
+}
 
 #[derive(Debug)]
 struct EditorState {
@@ -22,20 +224,26 @@ struct EditorState {
     editing: bool,
     edit_buffer: String,
     cursor: usize,
-    prompt_scroll: usize,
+    prompt_scroll: [usize; MULTILINE_FIELD_COUNT],
     status: String,
+    /// 修改主题字段后立即生效，便于预览
+    theme: Theme,
+    help: Option<HelpOverlay>,
 }
 
 impl EditorState {
     fn new(config: Config) -> Self {
+        let theme = Theme::from_config(&config).0;
         Self {
             config,
             selected: 0,
             editing: false,
             edit_buffer: String::new(),
             cursor: 0,
-            prompt_scroll: 0,
-            status: "F4 打开配置；Esc 返回搜索".to_string(),
+            prompt_scroll: [0; MULTILINE_FIELD_COUNT],
+            status: "已打开配置；Esc 返回搜索".to_string(),
+            theme,
+            help: None,
         }
     }
 
@@ -57,10 +265,16 @@ impl EditorState {
         );
     }
 
-    fn commit_edit(&mut self) {
-        set_field_value(&mut self.config, self.selected, self.edit_buffer.clone());
+    fn commit_edit(&mut self) -> bool {
+        if let Err(err) = set_field_value(&mut self.config, self.selected, self.edit_buffer.clone())
+        {
+            self.status = format!("{} 无效: {err}", self.selected_label());
+            return false;
+        }
         self.editing = false;
+        self.theme = Theme::from_config(&self.config).0;
         self.status = format!("已更新 {}，按 Ctrl+S 写入配置文件。", self.selected_label());
+        true
     }
 
     fn cancel_edit(&mut self) {
@@ -72,7 +286,12 @@ impl EditorState {
 
     fn commit_and_save(&mut self) {
         if self.editing {
-            set_field_value(&mut self.config, self.selected, self.edit_buffer.clone());
+            if let Err(err) =
+                set_field_value(&mut self.config, self.selected, self.edit_buffer.clone())
+            {
+                self.status = format!("{} 无效，未保存: {err}", self.selected_label());
+                return;
+            }
         }
 
         match self.config.save() {
@@ -86,6 +305,16 @@ impl EditorState {
         }
     }
 
+    fn scroll_prompt(&mut self, delta: isize) {
+        if let Some(scroll) = self
+            .selected
+            .checked_sub(FIRST_MULTILINE_FIELD)
+            .and_then(|idx| self.prompt_scroll.get_mut(idx))
+        {
+            *scroll = scroll.saturating_add_signed(delta);
+        }
+    }
+
     fn select_prev(&mut self) {
         if self.selected > 0 {
             self.selected -= 1;
@@ -119,71 +348,101 @@ pub fn run_config_editor(
             continue;
         }
 
-        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('s') {
-            state.commit_and_save();
+        if let Some(help) = state.help.as_mut() {
+            if help.handle_key(&key) {
+                state.help = None;
+            }
             continue;
         }
 
         if state.editing {
-            handle_edit_key(&mut state, key.code);
-        } else if handle_navigation_key(&mut state, key.code) {
+            handle_edit_key(&mut state, &key);
+        } else if handle_navigation_key(&mut state, &key) {
             return Ok(state.config);
         }
     }
 }
 
-fn handle_navigation_key(state: &mut EditorState, code: KeyCode) -> bool {
-    match code {
-        KeyCode::Esc => return true,
-        KeyCode::Up => state.select_prev(),
-        KeyCode::Down => state.select_next(),
-        KeyCode::PageUp if state.selected == FIELD_SYSTEM_PROMPT => {
-            state.prompt_scroll = state.prompt_scroll.saturating_sub(6);
-        }
-        KeyCode::PageDown if state.selected == FIELD_SYSTEM_PROMPT => {
-            state.prompt_scroll = state.prompt_scroll.saturating_add(6);
-        }
-        KeyCode::Home => state.selected = 0,
-        KeyCode::End => state.selected = FIELD_COUNT - 1,
-        KeyCode::Enter => state.begin_edit(),
-        _ => {}
+fn config_help() -> HelpOverlay {
+    HelpOverlay::new(
+        "配置页快捷键",
+        vec![
+            HelpSection::from_keys("选择字段", &NAVIGATION_KEYS, &[]),
+            HelpSection::from_keys("编辑中", &FIELD_EDIT_KEYS, &[("字符", "输入")]),
+        ],
+    )
+}
+
+/// 返回 `true` 表示返回搜索页。
+fn handle_navigation_key(state: &mut EditorState, key: &KeyEvent) -> bool {
+    let Some(command) = fixed_command(&NAVIGATION_KEYS, key) else {
+        return false;
+    };
+    match command {
+        NavCommand::Back => return true,
+        NavCommand::Prev => state.select_prev(),
+        NavCommand::Next => state.select_next(),
+        NavCommand::ScrollUp => state.scroll_prompt(-6),
+        NavCommand::ScrollDown => state.scroll_prompt(6),
+        NavCommand::First => state.selected = 0,
+        NavCommand::Last => state.selected = FIELD_COUNT - 1,
+        NavCommand::Edit => state.begin_edit(),
+        NavCommand::Save => state.commit_and_save(),
+        NavCommand::Help => state.help = Some(config_help()),
     }
 
     false
 }
 
-fn handle_edit_key(state: &mut EditorState, code: KeyCode) {
-    match code {
-        KeyCode::Esc => state.cancel_edit(),
-        KeyCode::F(2) => state.commit_edit(),
-        KeyCode::Tab => {
-            state.commit_edit();
-            state.select_next();
+fn handle_edit_key(state: &mut EditorState, key: &KeyEvent) {
+    let Some(command) = fixed_command(&FIELD_EDIT_KEYS, key) else {
+        let plain = !key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if let KeyCode::Char(ch) = key.code {
+            if plain && !ch.is_control() {
+                insert_char(&mut state.edit_buffer, &mut state.cursor, ch);
+            }
         }
-        KeyCode::BackTab => {
+        return;
+    };
+    let multiline = is_multiline(state.selected);
+    match command {
+        FieldEditCommand::Cancel => state.cancel_edit(),
+        FieldEditCommand::Finish => {
             state.commit_edit();
-            state.select_prev();
         }
-        KeyCode::Enter if state.selected == FIELD_SYSTEM_PROMPT => {
+        FieldEditCommand::NextField | FieldEditCommand::PrevField => {
+            if !state.commit_edit() {
+                return;
+            }
+            if command == FieldEditCommand::NextField {
+                state.select_next();
+            } else {
+                state.select_prev();
+            }
+        }
+        FieldEditCommand::Enter if multiline => {
             insert_char(&mut state.edit_buffer, &mut state.cursor, '\n');
         }
-        KeyCode::Enter => state.commit_edit(),
-        KeyCode::Backspace => backspace(&mut state.edit_buffer, &mut state.cursor),
-        KeyCode::Delete => delete_char(&mut state.edit_buffer, state.cursor),
-        KeyCode::Left => move_left(&state.edit_buffer, &mut state.cursor),
-        KeyCode::Right => move_right(&state.edit_buffer, &mut state.cursor),
-        KeyCode::Up if state.selected == FIELD_SYSTEM_PROMPT => {
+        FieldEditCommand::Enter => {
+            state.commit_edit();
+        }
+        FieldEditCommand::Save => state.commit_and_save(),
+        FieldEditCommand::Backspace => backspace(&mut state.edit_buffer, &mut state.cursor),
+        FieldEditCommand::Delete => delete_char(&mut state.edit_buffer, state.cursor),
+        FieldEditCommand::Left => move_left(&state.edit_buffer, &mut state.cursor),
+        FieldEditCommand::Right => move_right(&state.edit_buffer, &mut state.cursor),
+        FieldEditCommand::Up if multiline => {
             move_cursor_vertically(&state.edit_buffer, &mut state.cursor, -1);
         }
-        KeyCode::Down if state.selected == FIELD_SYSTEM_PROMPT => {
+        FieldEditCommand::Down if multiline => {
             move_cursor_vertically(&state.edit_buffer, &mut state.cursor, 1);
         }
-        KeyCode::Home => move_line_start(&state.edit_buffer, &mut state.cursor),
-        KeyCode::End => move_line_end(&state.edit_buffer, &mut state.cursor),
-        KeyCode::Char(ch) if !ch.is_control() => {
-            insert_char(&mut state.edit_buffer, &mut state.cursor, ch);
-        }
-        _ => {}
+        FieldEditCommand::Up | FieldEditCommand::Down => {}
+        FieldEditCommand::LineStart => move_line_start(&state.edit_buffer, &mut state.cursor),
+        FieldEditCommand::LineEnd => move_line_end(&state.edit_buffer, &mut state.cursor),
+        FieldEditCommand::Help => state.help = Some(config_help()),
     }
 }
 
@@ -199,12 +458,17 @@ fn draw_config_ui(frame: &mut Frame, state: &EditorState) {
         .split(frame.area());
 
     let header_text = if state.editing {
-        "AI 配置 | 编辑中: Enter/F2 完成，Tab 保存并切换，Ctrl+S 保存配置，Esc 取消"
+        "AI 配置 | 编辑中: Enter/F2 完成，Tab 保存并切换，Ctrl+S 保存配置，F1 帮助，Esc 取消"
     } else {
-        "AI 配置 | ↑/↓ 选择，Enter 编辑，PageUp/PageDown 滚动提示词，Ctrl+S 保存，Esc 返回"
+        "AI 配置 | ↑/↓ 选择，Enter 编辑，Ctrl+S 保存，F1/? 帮助，Esc 返回"
     };
     frame.render_widget(
-        Paragraph::new(header_text).block(Block::default().title("dict-tui").borders(Borders::ALL)),
+        Paragraph::new(header_text).block(
+            Block::default()
+                .title("dict-tui")
+                .borders(Borders::ALL)
+                .border_style(state.theme.border),
+        ),
         rows[0],
     );
 
@@ -213,34 +477,76 @@ fn draw_config_ui(frame: &mut Frame, state: &EditorState) {
         rows[1],
     );
 
+    let grid_rows = FIRST_MULTILINE_FIELD.div_ceil(GRID_COLUMNS);
+    let grid_height = (grid_rows as u16 * FIELD_HEIGHT)
+        .min(rows[2].height.saturating_sub(MIN_PROMPT_HEIGHT))
+        .max(FIELD_HEIGHT);
     let content_rows = Layout::default()
         .direction(Direction::Vertical)
+        .constraints([Constraint::Length(grid_height), Constraint::Min(3)])
+        .split(rows[2]);
+
+    draw_single_line_grid(frame, content_rows[0], state);
+
+    let prompt_columns = Layout::default()
+        .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Min(3),
+            Constraint::Percentage(24),
+            Constraint::Percentage(38),
+            Constraint::Percentage(38),
         ])
-        .split(rows[2]);
+        .split(content_rows[1]);
+    for (column, idx) in (FIRST_MULTILINE_FIELD..FIELD_COUNT).enumerate() {
+        draw_prompt_field(frame, prompt_columns[column], state, idx);
+    }
 
-    for (idx, area) in content_rows.iter().take(FIELD_SYSTEM_PROMPT).enumerate() {
-        draw_single_line_field(frame, *area, state, idx);
+    frame.render_widget(
+        Paragraph::new(state.status.as_str()).style(state.theme.status_style(&state.status)),
+        rows[3],
+    );
+
+    if let Some(help) = &state.help {
+        help.draw(frame, &state.theme);
     }
-    draw_prompt_field(frame, content_rows[FIELD_SYSTEM_PROMPT], state);
+}
+
+/// 单行字段按两列排布；空间不足时滚动到选中字段所在的行。
+fn draw_single_line_grid(frame: &mut Frame, area: Rect, state: &EditorState) {
+    let visible_rows = ((area.height / FIELD_HEIGHT) as usize).max(1);
+    let selected_row = state.selected.min(FIRST_MULTILINE_FIELD - 1) / GRID_COLUMNS;
+    let first_row = (selected_row + 1).saturating_sub(visible_rows);
 
-    frame.render_widget(Paragraph::new(state.status.as_str()), rows[3]);
+    let row_areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints((0..visible_rows).map(|_| Constraint::Length(FIELD_HEIGHT)))
+        .split(area);
+    for (offset, row_area) in row_areas.iter().enumerate() {
+        let column_areas = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints((0..GRID_COLUMNS).map(|_| Constraint::Ratio(1, GRID_COLUMNS as u32)))
+            .split(*row_area);
+        for (column, field_area) in column_areas.iter().enumerate() {
+            let idx = (first_row + offset) * GRID_COLUMNS + column;
+            if idx < FIRST_MULTILINE_FIELD {
+                draw_single_line_field(frame, *field_area, state, idx);
+            }
+        }
+    }
 }
 
 fn draw_single_line_field(frame: &mut Frame, area: Rect, state: &EditorState, idx: usize) {
     let selected = state.selected == idx;
     let editing = selected && state.editing;
-    let border_style = field_border_style(selected, editing);
+    let border_style = field_border_style(&state.theme, selected, editing);
     let inner = inner_rect(area);
     let display_value;
-    let value = if editing {
+    let mut cursor = state.cursor;
+    let value = if editing && idx == FIELD_API_KEY {
+        // 编辑密钥时同样遮盖，光标按字符数换算到遮盖后的文本上
+        display_value = "*".repeat(state.edit_buffer.chars().count());
+        cursor = state.edit_buffer[..state.cursor].chars().count();
+        display_value.as_str()
+    } else if editing {
         state.edit_buffer.as_str()
     } else {
         display_value = field_display_value(state, idx);
@@ -248,7 +554,7 @@ fn draw_single_line_field(frame: &mut Frame, area: Rect, state: &EditorState, id
     };
 
     let (text, cursor_x) = if editing {
-        single_line_view(value, state.cursor, inner.width)
+        single_line_view(value, cursor, inner.width)
     } else {
         (truncate_chars(value, inner.width as usize), 0)
     };
@@ -263,7 +569,7 @@ fn draw_single_line_field(frame: &mut Frame, area: Rect, state: &EditorState, id
         area,
     );
 
-    if editing && inner.width > 0 {
+    if editing && state.help.is_none() && inner.width > 0 {
         frame.set_cursor_position(Position::new(
             inner.x + cursor_x.min(inner.width.saturating_sub(1)),
             inner.y,
@@ -271,27 +577,40 @@ fn draw_single_line_field(frame: &mut Frame, area: Rect, state: &EditorState, id
     }
 }
 
-fn draw_prompt_field(frame: &mut Frame, area: Rect, state: &EditorState) {
-    let selected = state.selected == FIELD_SYSTEM_PROMPT;
+fn draw_prompt_field(frame: &mut Frame, area: Rect, state: &EditorState, idx: usize) {
+    let selected = state.selected == idx;
     let editing = selected && state.editing;
-    let border_style = field_border_style(selected, editing);
+    let border_style = field_border_style(&state.theme, selected, editing);
     let inner = inner_rect(area);
-    let value = if editing {
+    let display_value;
+    let mut cursor = state.cursor;
+    let value = if editing && idx == FIELD_CUSTOM_HEADERS {
+        // 与 API Key 一样，编辑时也遮盖请求头的值
+        (display_value, cursor) = mask_header_values(&state.edit_buffer, state.cursor);
+        display_value.as_str()
+    } else if editing {
         state.edit_buffer.as_str()
     } else {
-        state.config.system_prompt.as_str()
+        display_value = field_display_value(state, idx);
+        display_value.as_str()
     };
 
     let (text, cursor_x, cursor_y) = if editing {
-        multiline_view_at_cursor(value, state.cursor, inner.width, inner.height)
+        multiline_view_at_cursor(value, cursor, inner.width, inner.height)
     } else {
-        multiline_view_at_scroll(value, state.prompt_scroll, inner.width, inner.height)
+        let scroll = state.prompt_scroll[idx - FIRST_MULTILINE_FIELD];
+        multiline_view_at_scroll(value, scroll, inner.width, inner.height)
     };
 
     let title = if editing {
-        "系统提示词 (Enter 换行，F2 完成)"
+        format!("{} (Enter 换行，F2 完成)", field_label(idx))
+    } else if idx == FIELD_PROMPT_TEMPLATES {
+        format!(
+            "{} (=== 名称 === 开始一个模板；占位符 {{word}} {{language}} {{context}} {{definition}} {{source}})",
+            field_label(idx)
+        )
     } else {
-        "系统提示词"
+        field_label(idx).to_string()
     };
     frame.render_widget(
         Paragraph::new(text).wrap(Wrap { trim: false }).block(
@@ -303,7 +622,7 @@ fn draw_prompt_field(frame: &mut Frame, area: Rect, state: &EditorState) {
         area,
     );
 
-    if editing && inner.width > 0 && inner.height > 0 {
+    if editing && state.help.is_none() && inner.width > 0 && inner.height > 0 {
         frame.set_cursor_position(Position::new(
             inner.x + cursor_x.min(inner.width.saturating_sub(1)),
             inner.y + cursor_y.min(inner.height.saturating_sub(1)),
@@ -313,51 +632,193 @@ fn draw_prompt_field(frame: &mut Frame, area: Rect, state: &EditorState) {
 
 fn field_label(idx: usize) -> &'static str {
     match idx {
-        0 => "API 地址",
-        1 => "API Key",
-        2 => "模型",
-        3 => "回答语言",
+        FIELD_API_ENDPOINT => "API 地址",
+        FIELD_API_KEY => "API Key",
+        FIELD_API_KEY_ENV => "API Key 环境变量",
+        FIELD_API_KEY_FILE => "API Key 文件",
+        FIELD_MODEL => "模型",
+        FIELD_ANSWER_LANGUAGE => "回答语言",
         FIELD_WEB_SEARCH => "网页搜索",
         FIELD_JAVASCRIPT => "浏览器 JS",
+        FIELD_STRUCTURED => "结构化回答",
+        FIELD_REQUEST_TIMEOUT => "请求超时(秒)",
+        FIELD_CONNECT_TIMEOUT => "连接超时(秒)",
+        FIELD_MAX_RETRIES => "失败重试次数",
+        FIELD_PROXY => "代理 (http/socks5)",
+        FIELD_NO_PROXY => "不走代理的主机",
+        FIELD_CA_CERT => "CA 证书路径",
+        FIELD_CUSTOM_HEADERS => "自定义请求头 (每行 名称: 值)",
+        FIELD_ANKI_HTML => "Anki 背面保留 HTML",
+        FIELD_HISTORY_DWELL => "停留几秒记入历史 (0 不记录)",
+        FIELD_THEME => "主题 (dark/light/mono)",
+        FIELD_CLIPBOARD => "剪贴板命令 (OSC 52 之外)",
+        FIELD_LAYOUT_MODE => "布局 (auto/horizontal/vertical)",
+        FIELD_STACK_WIDTH => "窄于几列时上下排布",
+        FIELD_LIST_PERCENT => "结果列表占比 (15-85)",
         FIELD_SYSTEM_PROMPT => "系统提示词",
+        FIELD_PROMPT_TEMPLATES => "提示词模板",
         _ => "",
     }
 }
 
 fn field_value(config: &Config, idx: usize) -> String {
     match idx {
-        0 => config.api_endpoint.clone(),
-        1 => config.api_key.clone(),
-        2 => config.model.clone(),
-        3 => config.answer_language.clone(),
+        FIELD_API_ENDPOINT => config.api_endpoint.clone(),
+        FIELD_API_KEY => config.api_key.clone(),
+        FIELD_API_KEY_ENV => config.api_key_env.clone(),
+        FIELD_API_KEY_FILE => config.api_key_file.clone(),
+        FIELD_MODEL => config.model.clone(),
+        FIELD_ANSWER_LANGUAGE => config.answer_language.clone(),
         FIELD_WEB_SEARCH => bool_to_text(config.enable_web_search).to_string(),
         FIELD_JAVASCRIPT => bool_to_text(config.enable_javascript).to_string(),
+        FIELD_STRUCTURED => bool_to_text(config.structured_answers).to_string(),
+        FIELD_REQUEST_TIMEOUT => config.request_timeout_secs.to_string(),
+        FIELD_CONNECT_TIMEOUT => config.connect_timeout_secs.to_string(),
+        FIELD_MAX_RETRIES => config.max_retries.to_string(),
+        FIELD_PROXY => config.proxy.clone(),
+        FIELD_NO_PROXY => config.no_proxy.clone(),
+        FIELD_CA_CERT => config.ca_cert_path.clone(),
+        FIELD_CUSTOM_HEADERS => headers_to_text(&config.custom_headers),
+        FIELD_ANKI_HTML => bool_to_text(config.anki_html_back).to_string(),
+        FIELD_HISTORY_DWELL => config.history_dwell_secs.to_string(),
+        FIELD_THEME => config.theme.clone(),
+        FIELD_CLIPBOARD => config.clipboard_command.clone(),
+        FIELD_LAYOUT_MODE => config.layout_mode.name().to_string(),
+        FIELD_STACK_WIDTH => config.stack_below_width.to_string(),
+        FIELD_LIST_PERCENT => config.list_percent.to_string(),
         FIELD_SYSTEM_PROMPT => config.system_prompt.clone(),
+        FIELD_PROMPT_TEMPLATES => templates_to_text(&config.prompt_templates),
         _ => String::new(),
     }
 }
 
-fn set_field_value(config: &mut Config, idx: usize, value: String) {
+fn set_field_value(config: &mut Config, idx: usize, value: String) -> Result<(), String> {
     match idx {
-        0 => config.api_endpoint = value,
-        1 => config.api_key = value,
-        2 => config.model = value,
-        3 => config.answer_language = value,
+        FIELD_API_ENDPOINT => config.api_endpoint = value,
+        FIELD_API_KEY => config.api_key = value,
+        FIELD_API_KEY_ENV => config.api_key_env = value.trim().to_string(),
+        FIELD_API_KEY_FILE => config.api_key_file = value.trim().to_string(),
+        FIELD_MODEL => config.model = value,
+        FIELD_ANSWER_LANGUAGE => config.answer_language = value,
         FIELD_WEB_SEARCH => config.enable_web_search = parse_bool(&value),
         FIELD_JAVASCRIPT => config.enable_javascript = parse_bool(&value),
+        FIELD_STRUCTURED => config.structured_answers = parse_bool(&value),
+        FIELD_REQUEST_TIMEOUT => config.request_timeout_secs = parse_positive(&value)?,
+        FIELD_CONNECT_TIMEOUT => config.connect_timeout_secs = parse_positive(&value)?,
+        FIELD_MAX_RETRIES => {
+            config.max_retries = value
+                .trim()
+                .parse()
+                .map_err(|_| "请输入非负整数".to_string())?;
+        }
+        FIELD_PROXY => config.proxy = value.trim().to_string(),
+        FIELD_NO_PROXY => config.no_proxy = value.trim().to_string(),
+        FIELD_CA_CERT => config.ca_cert_path = value.trim().to_string(),
+        FIELD_CUSTOM_HEADERS => config.custom_headers = parse_headers_text(&value)?,
+        FIELD_ANKI_HTML => config.anki_html_back = parse_bool(&value),
+        FIELD_HISTORY_DWELL => {
+            config.history_dwell_secs = value
+                .trim()
+                .parse()
+                .map_err(|_| "请输入非负整数".to_string())?;
+        }
+        FIELD_THEME => {
+            let name = value.trim().to_ascii_lowercase();
+            if Theme::named(&name).is_none() {
+                return Err(format!("可选主题: {}", THEME_NAMES.join(", ")));
+            }
+            config.theme = name;
+        }
+        FIELD_CLIPBOARD => config.clipboard_command = value.trim().to_string(),
+        FIELD_LAYOUT_MODE => {
+            config.layout_mode = LayoutMode::from_name(&value).ok_or_else(|| {
+                let names: Vec<&str> = LayoutMode::ALL.iter().map(|mode| mode.name()).collect();
+                format!("可选布局: {}", names.join(", "))
+            })?;
+        }
+        FIELD_STACK_WIDTH => {
+            config.stack_below_width = value
+                .trim()
+                .parse()
+                .map_err(|_| "请输入非负整数".to_string())?;
+        }
+        FIELD_LIST_PERCENT => {
+            config.list_percent = value
+                .trim()
+                .parse()
+                .ok()
+                .filter(|percent| (15..=85).contains(percent))
+                .ok_or_else(|| "请输入 15 到 85 之间的整数".to_string())?;
+        }
         FIELD_SYSTEM_PROMPT => config.system_prompt = value,
+        FIELD_PROMPT_TEMPLATES => config.prompt_templates = parse_templates_text(&value)?,
         _ => {}
     }
+    Ok(())
+}
+
+fn is_multiline(idx: usize) -> bool {
+    idx >= FIRST_MULTILINE_FIELD
 }
 
+/// 不在编辑时显示的内容：密钥和请求头的值 (常是令牌) 遮盖后显示。
 fn field_display_value(state: &EditorState, idx: usize) -> String {
-    if idx == 1 && !state.config.api_key.is_empty() {
-        "*".repeat(state.config.api_key.chars().count().min(16))
-    } else {
-        field_value(&state.config, idx)
+    match idx {
+        FIELD_API_KEY => match state.config.api_key_source() {
+            Some(source) => format!("(使用{source})"),
+            None => mask_secret(&state.config.api_key),
+        },
+        FIELD_CUSTOM_HEADERS => state
+            .config
+            .custom_headers
+            .iter()
+            .map(|(name, value)| format!("{name}: {}", mask_secret(value)))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => field_value(&state.config, idx),
     }
 }
 
+/// 只保留末尾几位，其余用 `*` 代替；过短的密钥全部遮盖。
+fn mask_secret(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.is_empty() {
+        return String::new();
+    }
+    if chars.len() <= MASK_VISIBLE_CHARS * 2 {
+        return "*".repeat(8);
+    }
+    let tail: String = chars[chars.len() - MASK_VISIBLE_CHARS..].iter().collect();
+    format!("{}{tail}", "*".repeat(8))
+}
+
+/// 编辑中的请求头文本：每行第一个冒号后的字符逐个换成 `*` (空白保留)，
+/// 返回遮盖后的文本和换算后的光标位置。
+fn mask_header_values(text: &str, cursor: usize) -> (String, usize) {
+    let mut masked = String::with_capacity(text.len());
+    let mut masked_cursor = None;
+    let mut in_value = false;
+    for (idx, ch) in text.char_indices() {
+        if idx == cursor {
+            masked_cursor = Some(masked.len());
+        }
+        match ch {
+            '\n' => {
+                in_value = false;
+                masked.push(ch);
+            }
+            ':' if !in_value => {
+                in_value = true;
+                masked.push(ch);
+            }
+            _ if in_value && !ch.is_whitespace() => masked.push('*'),
+            _ => masked.push(ch),
+        }
+    }
+    let cursor = masked_cursor.unwrap_or(masked.len());
+    (masked, cursor)
+}
+
 fn bool_to_text(value: bool) -> &'static str {
     if value {
         "on"
@@ -373,15 +834,47 @@ fn parse_bool(value: &str) -> bool {
     ) || matches!(value.trim(), "是" | "开" | "开启" | "启用")
 }
 
-fn field_border_style(selected: bool, editing: bool) -> Style {
+fn parse_positive(value: &str) -> Result<u64, String> {
+    match value.trim().parse::<u64>() {
+        Ok(number) if number > 0 => Ok(number),
+        _ => Err("请输入正整数".to_string()),
+    }
+}
+
+/// 每行一个请求头。值中可以有 `;` `,` 等字符，换行不是合法的请求头字符，可以安全分隔。
+fn headers_to_text(headers: &BTreeMap<String, String>) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| format!("{name}: {value}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_headers_text(value: &str) -> Result<BTreeMap<String, String>, String> {
+    let mut headers = BTreeMap::new();
+    for item in value.lines().map(str::trim).filter(|item| !item.is_empty()) {
+        let (name, header_value) = item
+            .split_once(':')
+            .ok_or_else(|| format!("缺少冒号: {item}"))?;
+        let name = name.trim();
+        if HeaderName::from_bytes(name.as_bytes()).is_err() {
+            return Err(format!("请求头名称无效: {name}"));
+        }
+        if HeaderValue::from_str(header_value.trim()).is_err() {
+            return Err(format!("请求头 {name} 的值无效"));
+        }
+        headers.insert(name.to_string(), header_value.trim().to_string());
+    }
+    Ok(headers)
+}
+
+fn field_border_style(theme: &Theme, selected: bool, editing: bool) -> Style {
     if editing {
-        Style::default()
-            .fg(Color::Yellow)
-            .add_modifier(Modifier::BOLD)
+        theme.border_editing
     } else if selected {
-        Style::default().fg(Color::Cyan)
+        theme.border_focus
     } else {
-        Style::default()
+        theme.border
     }
 }
 
@@ -394,21 +887,6 @@ fn inner_rect(area: Rect) -> Rect {
     )
 }
 
-fn single_line_view(text: &str, cursor: usize, width: u16) -> (String, u16) {
-    let width = width as usize;
-    if width == 0 {
-        return (String::new(), 0);
-    }
-
-    let cursor_chars = text[..cursor].chars().count();
-    let scroll = cursor_chars.saturating_add(1).saturating_sub(width);
-    let visible = text.chars().skip(scroll).take(width).collect::<String>();
-    let cursor_x = cursor_chars
-        .saturating_sub(scroll)
-        .min(width.saturating_sub(1));
-    (visible, cursor_x as u16)
-}
-
 fn multiline_view_at_cursor(
     text: &str,
     cursor: usize,
@@ -462,60 +940,6 @@ fn truncate_chars(text: &str, max_chars: usize) -> String {
     text.chars().take(max_chars).collect()
 }
 
-fn insert_char(text: &mut String, cursor: &mut usize, ch: char) {
-    text.insert(*cursor, ch);
-    *cursor += ch.len_utf8();
-}
-
-fn backspace(text: &mut String, cursor: &mut usize) {
-    if *cursor == 0 {
-        return;
-    }
-
-    let prev = text[..*cursor]
-        .char_indices()
-        .last()
-        .map(|(idx, _)| idx)
-        .unwrap_or(0);
-    text.replace_range(prev..*cursor, "");
-    *cursor = prev;
-}
-
-fn delete_char(text: &mut String, cursor: usize) {
-    if cursor >= text.len() {
-        return;
-    }
-
-    let next = text[cursor..]
-        .char_indices()
-        .nth(1)
-        .map(|(idx, _)| cursor + idx)
-        .unwrap_or(text.len());
-    text.replace_range(cursor..next, "");
-}
-
-fn move_left(text: &str, cursor: &mut usize) {
-    if *cursor == 0 {
-        return;
-    }
-    *cursor = text[..*cursor]
-        .char_indices()
-        .last()
-        .map(|(idx, _)| idx)
-        .unwrap_or(0);
-}
-
-fn move_right(text: &str, cursor: &mut usize) {
-    if *cursor >= text.len() {
-        return;
-    }
-    *cursor = text[*cursor..]
-        .char_indices()
-        .nth(1)
-        .map(|(idx, _)| *cursor + idx)
-        .unwrap_or(text.len());
-}
-
 fn move_line_start(text: &str, cursor: &mut usize) {
     *cursor = text[..*cursor].rfind('\n').map(|idx| idx + 1).unwrap_or(0);
 }
@@ -582,3 +1006,52 @@ fn byte_index_for_col(line: &str, line_start: usize, target_col: usize) -> usize
         .map(|(idx, _)| line_start + idx)
         .unwrap_or(line_start + line.len())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headers_round_trip_one_per_line() {
+        let text = "X-Api-Version: 2024-01\n\n  Authorization: Bearer a;b,c  \n";
+        let headers = parse_headers_text(text).unwrap();
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers["Authorization"], "Bearer a;b,c");
+        assert_eq!(headers["X-Api-Version"], "2024-01");
+        assert_eq!(
+            parse_headers_text(&headers_to_text(&headers)).unwrap(),
+            headers
+        );
+    }
+
+    #[test]
+    fn value_may_contain_colons() {
+        let headers = parse_headers_text("X-Origin: https://example.com:8443").unwrap();
+        assert_eq!(headers["X-Origin"], "https://example.com:8443");
+    }
+
+    #[test]
+    fn invalid_headers_are_rejected() {
+        assert!(parse_headers_text("Authorization").is_err());
+        assert!(parse_headers_text("Bad Name: value").is_err());
+        assert!(parse_headers_text("X-Test: \u{7f}").is_err());
+        assert!(parse_headers_text("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn header_values_are_masked() {
+        assert_eq!(mask_secret("sk-1234567890abcd"), "********abcd");
+        assert_eq!(mask_secret("short"), "********");
+        assert_eq!(mask_secret(""), "");
+    }
+
+    #[test]
+    fn header_values_are_masked_while_editing() {
+        let text = "Authorization: Bearer 令牌\nX-Empty:";
+        let (masked, cursor) = mask_header_values(text, text.find('令').unwrap());
+        assert_eq!(masked, "Authorization: ****** **\nX-Empty:");
+        assert_eq!(&masked[cursor..cursor + 1], "*");
+        assert_eq!(cursor, "Authorization: ****** ".len());
+        assert_eq!(mask_header_values(text, text.len()).1, masked.len());
+    }
+}
@@ -0,0 +1,186 @@
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Paragraph, Wrap};
+use regex::{Regex, RegexBuilder};
+
+use crate::ui::theme::Theme;
+
+/// 详情栏内的一处匹配，`start`/`end` 为该行纯文本中的字节位置。
+#[derive(Debug, Clone, Copy)]
+struct FindMatch {
+    line: usize,
+    start: usize,
+    end: usize,
+}
+
+/// 详情栏内查找：高亮所有匹配 (不区分大小写)，n/N 在匹配之间跳转。
+#[derive(Debug, Default)]
+pub struct DetailFind {
+    pub pattern: String,
+    /// 正在输入查找内容
+    pub editing: bool,
+    matches: Vec<FindMatch>,
+    current: usize,
+}
+
+impl DetailFind {
+    pub fn new(pattern: String) -> Self {
+        Self {
+            pattern,
+            editing: true,
+            ..Self::default()
+        }
+    }
+
+    /// 重新在 `text` 中查找，当前匹配回到第一个。
+    pub fn update(&mut self, text: &Text) {
+        self.matches.clear();
+        self.current = 0;
+        let Some(re) = self.regex() else {
+            return;
+        };
+        for (line_idx, line) in text.lines.iter().enumerate() {
+            let plain = line_plain(line);
+            self.matches.extend(re.find_iter(&plain).map(|m| FindMatch {
+                line: line_idx,
+                start: m.start(),
+                end: m.end(),
+            }));
+        }
+    }
+
+    pub fn next(&mut self) {
+        if !self.matches.is_empty() {
+            self.current = (self.current + 1) % self.matches.len();
+        }
+    }
+
+    pub fn prev(&mut self) {
+        if !self.matches.is_empty() {
+            self.current = (self.current + self.matches.len() - 1) % self.matches.len();
+        }
+    }
+
+    pub fn status(&self) -> String {
+        if self.pattern.is_empty() {
+            "查找: (输入内容)".to_string()
+        } else if self.matches.is_empty() {
+            format!("查找 \"{}\": 无匹配", self.pattern)
+        } else {
+            format!(
+                "查找 \"{}\": {}/{}",
+                self.pattern,
+                self.current + 1,
+                self.matches.len()
+            )
+        }
+    }
+
+    /// 当前匹配在按 `width` 折行后的显示行号。
+    pub fn current_row(&self, text: &Text, width: u16) -> Option<usize> {
+        let found = self.matches.get(self.current)?;
+        if width == 0 {
+            return Some(found.line);
+        }
+        let rows_before: usize = text.lines[..found.line]
+            .iter()
+            .map(|line| wrapped_rows(line.clone(), width))
+            .sum();
+        // 截到匹配末尾后该行占用的行数，即匹配结束处所在的行
+        let partial = line_plain(&text.lines[found.line])[..found.end].to_string();
+        Some(rows_before + wrapped_rows(Line::from(partial), width) - 1)
+    }
+
+    /// 所有匹配使用主题的 `find_match` 样式，当前匹配使用 `find_current`。
+    pub fn highlight(&self, text: Text<'static>, theme: &Theme) -> Text<'static> {
+        if self.matches.is_empty() {
+            return text;
+        }
+        let mut lines = text.lines;
+        let mut idx = 0;
+        while idx < self.matches.len() {
+            let line_idx = self.matches[idx].line;
+            let mut ranges = Vec::new();
+            while idx < self.matches.len() && self.matches[idx].line == line_idx {
+                let found = self.matches[idx];
+                ranges.push((found.start, found.end, idx == self.current));
+                idx += 1;
+            }
+            if let Some(line) = lines.get_mut(line_idx) {
+                *line = highlight_line(std::mem::take(line), &ranges, theme);
+            }
+        }
+        Text::from(lines)
+    }
+
+    fn regex(&self) -> Option<Regex> {
+        if self.pattern.is_empty() {
+            return None;
+        }
+        RegexBuilder::new(&regex::escape(&self.pattern))
+            .case_insensitive(true)
+            .build()
+            .ok()
+    }
+}
+
+fn line_plain(line: &Line) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+fn wrapped_rows(line: Line, width: u16) -> usize {
+    Paragraph::new(line)
+        .wrap(Wrap { trim: false })
+        .line_count(width)
+        .max(1)
+}
+
+/// 按匹配的字节范围切分各个 span，保留原有样式并叠加高亮样式。
+fn highlight_line(
+    line: Line<'static>,
+    ranges: &[(usize, usize, bool)],
+    theme: &Theme,
+) -> Line<'static> {
+    let match_style = theme.find_match;
+    let current_style = theme.find_current;
+
+    let Line {
+        style,
+        alignment,
+        spans: original,
+    } = line;
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    for span in original {
+        let content = span.content.as_ref();
+        let span_end = offset + content.len();
+        let mut cursor = 0;
+        for &(start, end, current) in ranges {
+            let start = start.clamp(offset, span_end) - offset;
+            let end = end.clamp(offset, span_end) - offset;
+            if start >= end {
+                continue;
+            }
+            if start > cursor {
+                spans.push(Span::styled(content[cursor..start].to_string(), span.style));
+            }
+            let style = if current { current_style } else { match_style };
+            spans.push(Span::styled(
+                content[start..end].to_string(),
+                span.style.patch(style),
+            ));
+            cursor = end;
+        }
+        if cursor < content.len() {
+            spans.push(Span::styled(content[cursor..].to_string(), span.style));
+        }
+        offset = span_end;
+    }
+    Line {
+        style,
+        alignment,
+        spans,
+    }
+}
@@ -0,0 +1,140 @@
+use once_cell::sync::Lazy;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Paragraph, Widget};
+use regex::Regex;
+
+use crate::render::html::CrossRef;
+use crate::ui::theme::Theme;
+
+/// `/rʌn/`，或方括号内含有音标字符的 `[rʌn]`，前面须是行首、空白或冒号。
+static PHONETIC_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:^|[\s:：])(/[^/\s\d][^/\n\d]{0,40}/|\[[^\]\n\d]*[ˈˌːəɪʊæɑɔʌθðŋʃʒɛɜɒ][^\]\n\d]*\])")
+        .expect("valid phonetic regex")
+});
+const EXAMPLE_MARKERS: [&str; 8] = ["例", "e.g.", "eg.", "•", "◆", "◇", "▸", "»"];
+
+/// 词条详情的样式：首行词头，音标、例句行和交叉引用按主题着色。
+/// 交叉引用只匹配完整的词，并带下划线以便点击定位。
+pub fn entry_text(text: &str, links: &[CrossRef], theme: &Theme) -> Text<'static> {
+    Text::from(
+        text.lines()
+            .enumerate()
+            .map(|(idx, line)| entry_line(idx, line, links, theme))
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn entry_line(idx: usize, line: &str, links: &[CrossRef], theme: &Theme) -> Line<'static> {
+    if idx == 0 {
+        return Line::styled(line.to_string(), theme.headword);
+    }
+    let base = if is_example_line(line) {
+        theme.example
+    } else {
+        Style::default()
+    };
+    let link_style = theme.link.add_modifier(Modifier::UNDERLINED);
+
+    let mut ranges: Vec<(usize, usize, Style)> = links
+        .iter()
+        .flat_map(|link| {
+            line.match_indices(link.text.as_str())
+                .map(|(start, found)| (start, start + found.len(), link_style))
+        })
+        .filter(|&(start, end, _)| is_word_boundary(line, start, end))
+        .collect();
+    ranges.extend(PHONETIC_RE.captures_iter(line).filter_map(|caps| {
+        let found = caps.get(1)?;
+        Some((found.start(), found.end(), theme.phonetic))
+    }));
+    ranges.sort_unstable_by_key(|&(start, end, _)| (start, end));
+
+    let mut spans = Vec::new();
+    let mut cursor = 0;
+    for (start, end, style) in ranges {
+        if start < cursor {
+            continue;
+        }
+        if start > cursor {
+            spans.push(Span::styled(line[cursor..start].to_string(), base));
+        }
+        spans.push(Span::styled(line[start..end].to_string(), base.patch(style)));
+        cursor = end;
+    }
+    if cursor < line.len() {
+        spans.push(Span::styled(line[cursor..].to_string(), base));
+    }
+    Line::from(spans)
+}
+
+fn is_example_line(line: &str) -> bool {
+    let line = line.trim_start();
+    EXAMPLE_MARKERS
+        .iter()
+        .any(|marker| line.starts_with(marker))
+}
+
+fn is_word_boundary(line: &str, start: usize, end: usize) -> bool {
+    let before = line[..start].chars().next_back();
+    let after = line[end..].chars().next();
+    !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+}
+
+/// 返回点击位置下的交叉引用。
+///
+/// 重新把详情段落渲染到临时缓冲区，从点击的格子向两侧取出带下划线的文字，
+/// 这样折行后的位置和屏幕上完全一致。
+pub fn link_at<'a>(
+    paragraph: Paragraph,
+    area: Rect,
+    position: Position,
+    links: &'a [CrossRef],
+) -> Option<&'a CrossRef> {
+    if links.is_empty() || !area.contains(position) {
+        return None;
+    }
+    let mut buffer = Buffer::empty(area);
+    paragraph.render(area, &mut buffer);
+
+    // 宽字符后面的格子被清空，按字符宽度跳过
+    let mut cells = Vec::new();
+    let mut x = area.x;
+    while x < area.right() {
+        let cell = buffer.cell((x, position.y))?;
+        let width = Span::raw(cell.symbol()).width().max(1) as u16;
+        let underlined = cell.modifier.contains(Modifier::UNDERLINED);
+        cells.push((x, width, cell.symbol().to_string(), underlined));
+        x += width;
+    }
+
+    let hit = cells
+        .iter()
+        .position(|&(x, width, _, _)| (x..x + width).contains(&position.x))?;
+    if !cells[hit].3 {
+        return None;
+    }
+    let start = cells[..hit]
+        .iter()
+        .rposition(|cell| !cell.3)
+        .map_or(0, |idx| idx + 1);
+    let end = cells[hit..]
+        .iter()
+        .position(|cell| !cell.3)
+        .map_or(cells.len(), |idx| hit + idx);
+    let fragment: String = cells[start..end]
+        .iter()
+        .map(|cell| cell.2.as_str())
+        .collect();
+    let fragment = fragment.trim();
+    if fragment.is_empty() {
+        return None;
+    }
+
+    links
+        .iter()
+        .find(|link| link.text == fragment)
+        .or_else(|| links.iter().find(|link| link.text.contains(fragment)))
+}
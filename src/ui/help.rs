@@ -0,0 +1,150 @@
+use std::cell::Cell;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::ui::keymap::FixedKey;
+use crate::ui::theme::Theme;
+
+const PAGE_STEP: usize = 10;
+
+/// 帮助中的一组按键，每项为 (按键, 说明)。
+#[derive(Debug)]
+pub struct HelpSection {
+    pub title: String,
+    pub entries: Vec<(String, String)>,
+}
+
+impl HelpSection {
+    pub fn new(title: &str, entries: Vec<(String, String)>) -> Self {
+        Self {
+            title: title.to_string(),
+            entries,
+        }
+    }
+
+    /// 由页面的按键表生成；`notes` 补充不是固定按键的操作，如输入字符。
+    pub fn from_keys<C>(title: &str, table: &[FixedKey<C>], notes: &[(&str, &str)]) -> Self {
+        let keys = table
+            .iter()
+            .map(|entry| (entry.keys_text(), entry.label.to_string()));
+        let notes = notes
+            .iter()
+            .map(|(keys, label)| (keys.to_string(), label.to_string()));
+        Self::new(title, keys.chain(notes).collect())
+    }
+}
+
+/// 居中的帮助窗口，按分组列出当前页面的全部按键，可滚动。
+#[derive(Debug)]
+pub struct HelpOverlay {
+    title: String,
+    sections: Vec<HelpSection>,
+    scroll: usize,
+    /// 上次绘制时的最大滚动位置
+    max_scroll: Cell<usize>,
+}
+
+impl HelpOverlay {
+    pub fn new(title: &str, sections: Vec<HelpSection>) -> Self {
+        Self {
+            title: title.to_string(),
+            sections,
+            scroll: 0,
+            max_scroll: Cell::new(usize::MAX),
+        }
+    }
+
+    /// 处理按键，返回 `true` 表示关闭帮助。
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let max_scroll = self.max_scroll.get();
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::F(1) | KeyCode::Char('q' | '?') => {
+                return true;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = (self.scroll + 1).min(max_scroll),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(PAGE_STEP),
+            KeyCode::PageDown => self.scroll = (self.scroll + PAGE_STEP).min(max_scroll),
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::End => self.scroll = max_scroll,
+            _ => {}
+        }
+        false
+    }
+
+    pub fn draw(&self, frame: &mut Frame, theme: &Theme) {
+        let lines = self.lines(theme);
+        let content_width = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+        let area = frame.area();
+        let width = content_width.saturating_add(4).max(30).min(area.width);
+        let height = (lines.len() as u16)
+            .saturating_add(2)
+            .min(area.height.saturating_sub(2).max(3));
+        let popup = Rect::new(
+            area.x + (area.width.saturating_sub(width)) / 2,
+            area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height.min(area.height),
+        );
+
+        let visible = popup.height.saturating_sub(2) as usize;
+        let max_scroll = lines.len().saturating_sub(visible);
+        self.max_scroll.set(max_scroll);
+        let scroll = self.scroll.min(max_scroll);
+        let title = if max_scroll > 0 {
+            format!(
+                "{} ({}/{}，↑↓ 滚动，Esc 关闭)",
+                self.title,
+                scroll + 1,
+                max_scroll + 1
+            )
+        } else {
+            format!("{} (Esc 关闭)", self.title)
+        };
+
+        frame.render_widget(Clear, popup);
+        frame.render_widget(
+            Paragraph::new(Text::from(lines))
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(theme.border_focus),
+                )
+                .scroll((scroll.min(u16::MAX as usize) as u16, 0)),
+            popup,
+        );
+    }
+
+    fn lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        let key_width = self
+            .sections
+            .iter()
+            .flat_map(|section| &section.entries)
+            .map(|(keys, _)| Span::raw(keys.as_str()).width())
+            .max()
+            .unwrap_or(0);
+
+        let mut lines = Vec::new();
+        for section in &self.sections {
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::styled(section.title.clone(), theme.headword));
+            for (keys, label) in &section.entries {
+                let padding = key_width.saturating_sub(Span::raw(keys.as_str()).width());
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(keys.clone(), Style::default().add_modifier(Modifier::BOLD)),
+                    Span::raw(format!("{}  {label}", " ".repeat(padding))),
+                ]));
+            }
+        }
+        lines
+    }
+}
@@ -0,0 +1,424 @@
+use std::io;
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::text::{Line, Text};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{backend::CrosstermBackend, Frame, Terminal};
+
+use crate::app::time::{format_timestamp, now_secs};
+use crate::app::Config;
+use crate::cache::DefinitionCache;
+use crate::dict::DictionaryStore;
+use crate::export::export_history;
+use crate::history::{HistorySort, LookupHistory, LookupKind, WordStats};
+use crate::notebook::Notebook;
+use crate::ui::help::{HelpOverlay, HelpSection};
+use crate::ui::keymap::{fixed_command, FixedKey};
+use crate::ui::theme::Theme;
+
+const PAGE_STEP: usize = 10;
+const WEEK_SECS: u64 = 7 * 24 * 60 * 60;
+const RECENT_RECORDS: usize = 10;
+const HELP_TEXT: &str =
+    "输入过滤 | Tab 切换最近/次数排序 | Enter 查词 | Delete 删除该词记录 | Ctrl+X 导出 Anki | F1 帮助 | Esc 返回";
+/// 查询历史页的按键，按键处理和帮助窗口共用。
+const HISTORY_KEYS: [FixedKey<HistoryCommand>; 13] = [
+    FixedKey {
+        keys: &["Enter"],
+        label: "查询该词",
+        command: HistoryCommand::Open,
+    },
+    FixedKey {
+        keys: &["Tab"],
+        label: "切换最近/次数排序",
+        command: HistoryCommand::ToggleSort,
+    },
+    FixedKey {
+        keys: &["Delete"],
+        label: "删除该词的全部记录",
+        command: HistoryCommand::Delete,
+    },
+    FixedKey {
+        keys: &["Ctrl+X"],
+        label: "导出为 Anki 卡片",
+        command: HistoryCommand::Export,
+    },
+    FixedKey {
+        keys: &["Up"],
+        label: "上一个单词",
+        command: HistoryCommand::Up,
+    },
+    FixedKey {
+        keys: &["Down"],
+        label: "下一个单词",
+        command: HistoryCommand::Down,
+    },
+    FixedKey {
+        keys: &["PageUp"],
+        label: "上翻页",
+        command: HistoryCommand::PageUp,
+    },
+    FixedKey {
+        keys: &["PageDown"],
+        label: "下翻页",
+        command: HistoryCommand::PageDown,
+    },
+    FixedKey {
+        keys: &["Home"],
+        label: "第一个单词",
+        command: HistoryCommand::First,
+    },
+    FixedKey {
+        keys: &["End"],
+        label: "最后一个单词",
+        command: HistoryCommand::Last,
+    },
+    FixedKey {
+        keys: &["Backspace"],
+        label: "删除过滤条件的最后一个字符",
+        command: HistoryCommand::DeleteFilterChar,
+    },
+    FixedKey {
+        keys: &["F1"],
+        label: "帮助",
+        command: HistoryCommand::Help,
+    },
+    FixedKey {
+        keys: &["Esc"],
+        label: "返回搜索",
+        command: HistoryCommand::Back,
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HistoryCommand {
+    Open,
+    ToggleSort,
+    Delete,
+    Export,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    First,
+    Last,
+    DeleteFilterChar,
+    Help,
+    Back,
+}
+
+struct HistoryState {
+    filter: String,
+    sort: HistorySort,
+    words: Vec<WordStats>,
+    total_words: usize,
+    selected: usize,
+    status: String,
+    help: Option<HelpOverlay>,
+    theme: Theme,
+    /// 搜索页加入生词本的按键，用于详情中的提示
+    save_keys: String,
+}
+
+impl HistoryState {
+    fn refresh(&mut self, history: &LookupHistory) {
+        self.words = history.word_stats(self.sort, &self.filter);
+        self.total_words = if self.filter.is_empty() {
+            self.words.len()
+        } else {
+            history.word_stats(self.sort, "").len()
+        };
+        self.selected = self.selected.min(self.words.len().saturating_sub(1));
+    }
+
+    fn selected_word(&self) -> Option<&WordStats> {
+        self.words.get(self.selected)
+    }
+}
+
+/// 浏览查询历史。返回用户选择重新查询的单词，Esc 返回 `None`。
+pub fn run_history(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    history: &mut LookupHistory,
+    notebook: &Notebook,
+    store: &DictionaryStore,
+    definition_cache: &mut DefinitionCache,
+    config: &Config,
+    save_keys: String,
+) -> Result<Option<String>> {
+    let mut state = HistoryState {
+        filter: String::new(),
+        sort: HistorySort::Recent,
+        words: Vec::new(),
+        total_words: 0,
+        selected: 0,
+        status: HELP_TEXT.to_string(),
+        help: None,
+        theme: Theme::from_config(config).0,
+        save_keys,
+    };
+    state.refresh(history);
+
+    loop {
+        terminal.draw(|frame| draw_history_ui(frame, history, notebook, &state))?;
+
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        if let Some(help) = state.help.as_mut() {
+            if help.handle_key(&key) {
+                state.help = None;
+            }
+            continue;
+        }
+
+        let Some(command) = fixed_command(&HISTORY_KEYS, &key) else {
+            if let KeyCode::Char(ch) = key.code {
+                if !ch.is_control() && !key.modifiers.contains(KeyModifiers::CONTROL) {
+                    state.filter.push(ch);
+                    state.selected = 0;
+                    state.refresh(history);
+                }
+            }
+            continue;
+        };
+        match command {
+            HistoryCommand::Back => return Ok(None),
+            HistoryCommand::Open => {
+                if let Some(stats) = state.selected_word() {
+                    return Ok(Some(stats.word.clone()));
+                }
+            }
+            HistoryCommand::ToggleSort => {
+                state.sort = state.sort.toggle();
+                state.selected = 0;
+                state.refresh(history);
+            }
+            HistoryCommand::Delete => delete_selected(&mut state, history),
+            HistoryCommand::Export => {
+                state.status = "正在导出 Anki 卡片...".to_string();
+                terminal.draw(|frame| draw_history_ui(frame, history, notebook, &state))?;
+                state.status = match export_history(
+                    &state.words,
+                    store,
+                    definition_cache,
+                    config.anki_html_back,
+                ) {
+                    Ok(export) => export.summary(),
+                    Err(err) => format!("导出 Anki 失败: {err}"),
+                };
+            }
+            HistoryCommand::Up => state.selected = state.selected.saturating_sub(1),
+            HistoryCommand::Down => {
+                state.selected = (state.selected + 1).min(state.words.len().saturating_sub(1));
+            }
+            HistoryCommand::PageUp => state.selected = state.selected.saturating_sub(PAGE_STEP),
+            HistoryCommand::PageDown => {
+                state.selected =
+                    (state.selected + PAGE_STEP).min(state.words.len().saturating_sub(1));
+            }
+            HistoryCommand::First => state.selected = 0,
+            HistoryCommand::Last => state.selected = state.words.len().saturating_sub(1),
+            HistoryCommand::DeleteFilterChar => {
+                state.filter.pop();
+                state.selected = 0;
+                state.refresh(history);
+            }
+            HistoryCommand::Help => {
+                state.help = Some(HelpOverlay::new(
+                    "查询历史快捷键",
+                    vec![HelpSection::from_keys(
+                        "查询历史",
+                        &HISTORY_KEYS,
+                        &[("字符", "输入过滤条件")],
+                    )],
+                ));
+            }
+        }
+    }
+}
+
+fn delete_selected(state: &mut HistoryState, history: &mut LookupHistory) {
+    let Some(word) = state.selected_word().map(|stats| stats.word.clone()) else {
+        return;
+    };
+    let removed = history.remove_word(&word);
+    state.status = match history.save() {
+        Ok(()) => format!("已删除 {word} 的 {removed} 条记录"),
+        Err(err) => format!("保存查询历史失败: {err}"),
+    };
+    state.refresh(history);
+}
+
+fn in_notebook(notebook: &Notebook, word: &str) -> bool {
+    notebook
+        .entries()
+        .iter()
+        .any(|entry| entry.word.eq_ignore_ascii_case(word))
+}
+
+fn draw_history_ui(
+    frame: &mut Frame,
+    history: &LookupHistory,
+    notebook: &Notebook,
+    state: &HistoryState,
+) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ])
+        .split(frame.area());
+
+    let title = format!(
+        "查询历史 (共 {} 次，{} 个单词，近 7 天 {} 次)",
+        history.records().len(),
+        state.total_words,
+        history.count_since(now_secs().saturating_sub(WEEK_SECS))
+    );
+    frame.render_widget(
+        Paragraph::new(state.filter.as_str())
+            .block(Block::default().title(title).borders(Borders::ALL)),
+        rows[0],
+    );
+    frame.render_widget(Paragraph::new(state.status.as_str()), rows[1]);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(rows[2]);
+
+    // 按次数排序时，未收入生词本的高频词就是需要补的词汇
+    let gap_style = state.theme.accent;
+    let list_items: Vec<ListItem> = if state.words.is_empty() {
+        vec![ListItem::new("还没有查询记录")]
+    } else {
+        state
+            .words
+            .iter()
+            .map(|stats| {
+                let item = match state.sort {
+                    HistorySort::Recent => ListItem::new(format!(
+                        "{}  {}",
+                        stats.word,
+                        format_timestamp(stats.last_at)
+                    )),
+                    HistorySort::Frequency => {
+                        ListItem::new(format!("{:>4} 次  {}", stats.count, stats.word))
+                    }
+                };
+                if state.sort == HistorySort::Frequency && !in_notebook(notebook, &stats.word) {
+                    item.style(gap_style)
+                } else {
+                    item
+                }
+            })
+            .collect()
+    };
+
+    let list = List::new(list_items)
+        .block(
+            Block::default()
+                .title(format!("{} (Tab 切换)", state.sort.label()))
+                .borders(Borders::ALL),
+        )
+        .highlight_style(state.theme.highlight)
+        .highlight_symbol(">> ");
+    let mut list_state = ListState::default();
+    if !state.words.is_empty() {
+        list_state.select(Some(state.selected));
+    }
+    frame.render_stateful_widget(list, columns[0], &mut list_state);
+
+    let detail = state
+        .selected_word()
+        .map(|stats| build_detail(history, notebook, stats, state))
+        .unwrap_or_default();
+    frame.render_widget(
+        Paragraph::new(detail)
+            .block(Block::default().title("统计").borders(Borders::ALL))
+            .wrap(Wrap { trim: false }),
+        columns[1],
+    );
+    if let Some(help) = &state.help {
+        help.draw(frame, &state.theme);
+    }
+}
+
+fn build_detail(
+    history: &LookupHistory,
+    notebook: &Notebook,
+    stats: &WordStats,
+    state: &HistoryState,
+) -> Text<'static> {
+    let theme = &state.theme;
+    let heading = theme.headword;
+    let mut lines = vec![Line::styled(stats.word.clone(), heading)];
+    if !stats.source.is_empty() {
+        lines.push(Line::from(format!("来源词典: {}", stats.source)));
+    }
+    lines.push(Line::from(format!("查询 {} 次", stats.count)));
+    for kind in LookupKind::ALL {
+        lines.push(Line::from(format!(
+            "  {}: {}",
+            kind.label(),
+            stats.kind_count(kind)
+        )));
+    }
+    lines.push(Line::from(format!(
+        "首次: {}",
+        format_timestamp(stats.first_at)
+    )));
+    lines.push(Line::from(format!(
+        "最近: {}",
+        format_timestamp(stats.last_at)
+    )));
+    lines.push(if in_notebook(notebook, &stats.word) {
+        Line::from("已在生词本中")
+    } else {
+        let open_keys = HISTORY_KEYS
+            .iter()
+            .find(|entry| entry.command == HistoryCommand::Open)
+            .map(|entry| entry.keys_text())
+            .unwrap_or_default();
+        Line::styled(
+            format!(
+                "未加入生词本 ({open_keys} 查词后按 {} 加入)",
+                state.save_keys
+            ),
+            theme.accent,
+        )
+    });
+
+    lines.push(Line::default());
+    lines.push(Line::styled("最近记录", heading));
+    lines.extend(
+        history
+            .records()
+            .iter()
+            .rev()
+            .filter(|record| record.word.eq_ignore_ascii_case(&stats.word))
+            .take(RECENT_RECORDS)
+            .map(|record| {
+                Line::from(format!(
+                    "{}  {}",
+                    format_timestamp(record.at),
+                    record.kind.label()
+                ))
+            }),
+    );
+    Text::from(lines)
+}
@@ -0,0 +1,716 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::ui::help::HelpSection;
+
+/// 搜索页上可以绑定按键的操作。配置文件中使用 `name()` 返回的名称。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Help,
+    Quit,
+    PrevEntry,
+    NextEntry,
+    FirstEntry,
+    LastEntry,
+    PageUp,
+    PageDown,
+    ScrollUp,
+    ScrollDown,
+    ToggleZoom,
+    CycleLayout,
+    ShrinkList,
+    GrowList,
+    NewTab,
+    CloseTab,
+    NextTab,
+    PrevTab,
+    OpenBrowser,
+    AiQuery,
+    AiRefresh,
+    AiCancel,
+    AiNext,
+    AiPrev,
+    TemplatePicker,
+    Find,
+    SaveWord,
+    CopyWord,
+    CopyDefinition,
+    CopyAi,
+    Notebook,
+    Review,
+    History,
+    AiHistory,
+    Config,
+}
+
+impl Action {
+    pub const ALL: [Action; 35] = [
+        Action::Help,
+        Action::Quit,
+        Action::PrevEntry,
+        Action::NextEntry,
+        Action::FirstEntry,
+        Action::LastEntry,
+        Action::PageUp,
+        Action::PageDown,
+        Action::ScrollUp,
+        Action::ScrollDown,
+        Action::ToggleZoom,
+        Action::CycleLayout,
+        Action::ShrinkList,
+        Action::GrowList,
+        Action::NewTab,
+        Action::CloseTab,
+        Action::NextTab,
+        Action::PrevTab,
+        Action::OpenBrowser,
+        Action::AiQuery,
+        Action::AiRefresh,
+        Action::AiCancel,
+        Action::AiNext,
+        Action::AiPrev,
+        Action::TemplatePicker,
+        Action::Find,
+        Action::SaveWord,
+        Action::CopyWord,
+        Action::CopyDefinition,
+        Action::CopyAi,
+        Action::Notebook,
+        Action::Review,
+        Action::History,
+        Action::AiHistory,
+        Action::Config,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Help => "help",
+            Action::Quit => "quit",
+            Action::PrevEntry => "prev-entry",
+            Action::NextEntry => "next-entry",
+            Action::FirstEntry => "first-entry",
+            Action::LastEntry => "last-entry",
+            Action::PageUp => "page-up",
+            Action::PageDown => "page-down",
+            Action::ScrollUp => "scroll-up",
+            Action::ScrollDown => "scroll-down",
+            Action::ToggleZoom => "zoom",
+            Action::CycleLayout => "cycle-layout",
+            Action::ShrinkList => "shrink-list",
+            Action::GrowList => "grow-list",
+            Action::NewTab => "new-tab",
+            Action::CloseTab => "close-tab",
+            Action::NextTab => "next-tab",
+            Action::PrevTab => "prev-tab",
+            Action::OpenBrowser => "open-browser",
+            Action::AiQuery => "ai-query",
+            Action::AiRefresh => "ai-refresh",
+            Action::AiCancel => "ai-cancel",
+            Action::AiNext => "ai-next",
+            Action::AiPrev => "ai-prev",
+            Action::TemplatePicker => "template-picker",
+            Action::Find => "find",
+            Action::SaveWord => "save-word",
+            Action::CopyWord => "copy-word",
+            Action::CopyDefinition => "copy-definition",
+            Action::CopyAi => "copy-ai",
+            Action::Notebook => "notebook",
+            Action::Review => "review",
+            Action::History => "history",
+            Action::AiHistory => "ai-history",
+            Action::Config => "config",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::Help => "帮助",
+            Action::Quit => "退出",
+            Action::PrevEntry => "上一词条",
+            Action::NextEntry => "下一词条",
+            Action::FirstEntry => "首条",
+            Action::LastEntry => "末条",
+            Action::PageUp => "上翻页",
+            Action::PageDown => "下翻页",
+            Action::ScrollUp => "向上滚动详情",
+            Action::ScrollDown => "向下滚动详情",
+            Action::ToggleZoom => "只看详情",
+            Action::CycleLayout => "切换布局",
+            Action::ShrinkList => "缩小结果列表",
+            Action::GrowList => "放大结果列表",
+            Action::NewTab => "新标签页",
+            Action::CloseTab => "关闭标签页",
+            Action::NextTab => "下一个标签页",
+            Action::PrevTab => "上一个标签页",
+            Action::OpenBrowser => "网页预览",
+            Action::AiQuery => "AI查询",
+            Action::AiRefresh => "刷新AI",
+            Action::AiCancel => "取消AI",
+            Action::AiNext => "下一个AI结果",
+            Action::AiPrev => "上一个AI结果",
+            Action::TemplatePicker => "提示词模板",
+            Action::Find => "详情内查找",
+            Action::SaveWord => "存生词",
+            Action::CopyWord => "复制词头",
+            Action::CopyDefinition => "复制释义",
+            Action::CopyAi => "复制AI回答",
+            Action::Notebook => "生词本",
+            Action::Review => "复习",
+            Action::History => "历史",
+            Action::AiHistory => "AI缓存",
+            Action::Config => "配置",
+        }
+    }
+
+    /// 翻页、首末条等通用按键和不常用的复制操作不占用提示行。
+    fn in_tip(self) -> bool {
+        !matches!(
+            self,
+            Action::FirstEntry
+                | Action::LastEntry
+                | Action::PageUp
+                | Action::PageDown
+                | Action::CopyWord
+                | Action::CopyAi
+                | Action::CycleLayout
+                | Action::ShrinkList
+                | Action::GrowList
+                | Action::CloseTab
+                | Action::NextTab
+                | Action::PrevTab
+        )
+    }
+
+    /// 帮助窗口中的分组。
+    fn category(self) -> &'static str {
+        match self {
+            Action::PrevEntry
+            | Action::NextEntry
+            | Action::FirstEntry
+            | Action::LastEntry
+            | Action::PageUp
+            | Action::PageDown
+            | Action::ScrollUp
+            | Action::ScrollDown => "浏览词条",
+            Action::ToggleZoom | Action::CycleLayout | Action::ShrinkList | Action::GrowList => {
+                "布局"
+            }
+            Action::NewTab | Action::CloseTab | Action::NextTab | Action::PrevTab => "标签页",
+            Action::OpenBrowser
+            | Action::Find
+            | Action::SaveWord
+            | Action::CopyWord
+            | Action::CopyDefinition
+            | Action::CopyAi => "当前词条",
+            Action::AiQuery
+            | Action::AiRefresh
+            | Action::AiCancel
+            | Action::AiNext
+            | Action::AiPrev
+            | Action::TemplatePicker => "AI",
+            Action::Help
+            | Action::Quit
+            | Action::Notebook
+            | Action::Review
+            | Action::History
+            | Action::AiHistory
+            | Action::Config => "页面",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Action::ALL
+            .into_iter()
+            .find(|action| action.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Help => &["F1"],
+            Action::Quit => &["Esc", "Ctrl+C"],
+            Action::PrevEntry => &["Shift+Tab", "Alt+Up"],
+            Action::NextEntry => &["Tab", "Alt+Down"],
+            Action::FirstEntry => &["Home"],
+            Action::LastEntry => &["End"],
+            Action::PageUp => &["PageUp"],
+            Action::PageDown => &["PageDown"],
+            Action::ScrollUp => &["Up"],
+            Action::ScrollDown => &["Down"],
+            Action::ToggleZoom => &["Alt+Z"],
+            Action::CycleLayout => &["Alt+L"],
+            Action::ShrinkList => &["Alt+Left"],
+            Action::GrowList => &["Alt+Right"],
+            Action::NewTab => &["Ctrl+T"],
+            Action::CloseTab => &["Alt+W"],
+            Action::NextTab => &["Ctrl+PageDown", "Alt+."],
+            Action::PrevTab => &["Ctrl+PageUp", "Alt+,"],
+            Action::OpenBrowser => &["Ctrl+O", "F2"],
+            Action::AiQuery => &["Ctrl+G"],
+            Action::AiRefresh => &["Ctrl+R"],
+            Action::AiCancel => &["Ctrl+X"],
+            Action::AiNext => &["Ctrl+N"],
+            Action::AiPrev => &["Ctrl+P"],
+            Action::TemplatePicker => &["F3"],
+            Action::Find => &["Ctrl+F"],
+            Action::SaveWord => &["Ctrl+S"],
+            Action::CopyWord => &["Alt+C"],
+            Action::CopyDefinition => &["Ctrl+Y"],
+            Action::CopyAi => &["Alt+Y"],
+            Action::Notebook => &["F6"],
+            Action::Review => &["F7"],
+            Action::History => &["F8"],
+            Action::AiHistory => &["F5"],
+            Action::Config => &["F4"],
+        }
+    }
+}
+
+/// 一个按键组合。字符键忽略 Shift (由字符本身的大小写区分)。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Char(ch) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                // 终端在按下 Ctrl/Alt 时报告的字母大小写不固定
+                if modifiers.is_empty() {
+                    KeyCode::Char(ch)
+                } else {
+                    KeyCode::Char(ch.to_ascii_lowercase())
+                }
+            }
+            KeyCode::BackTab => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+
+    pub fn from_event(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+
+    /// 解析 `Ctrl+G`、`Alt+Down`、`F2`、`Shift+Tab`、`,` 这样的写法。
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err("按键为空".to_string());
+        }
+        // 最后一段是按键本身，允许按键就是 `+`
+        let (prefix, key) = match text.rsplit_once('+') {
+            Some((prefix, "")) => (prefix.strip_suffix('+').unwrap_or(prefix), "+"),
+            Some((prefix, key)) => (prefix, key),
+            None => ("", text),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for part in prefix.split('+').filter(|part| !part.is_empty()) {
+            match part.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" | "meta" | "option" => modifiers |= KeyModifiers::ALT,
+                "shift" => modifiers |= KeyModifiers::SHIFT,
+                other => return Err(format!("无法识别的修饰键: {other}")),
+            }
+        }
+
+        let code = match key.to_ascii_lowercase().as_str() {
+            "esc" | "escape" => KeyCode::Esc,
+            "enter" | "return" => KeyCode::Enter,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" | "ins" => KeyCode::Insert,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" | "pgup" => KeyCode::PageUp,
+            "pagedown" | "pgdn" => KeyCode::PageDown,
+            "up" | "↑" => KeyCode::Up,
+            "down" | "↓" => KeyCode::Down,
+            "left" | "←" => KeyCode::Left,
+            "right" | "→" => KeyCode::Right,
+            "space" => KeyCode::Char(' '),
+            lower => {
+                if let Some(number) = lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    if (1..=24).contains(&number) {
+                        KeyCode::F(number)
+                    } else {
+                        return Err(format!("不支持的功能键: {key}"));
+                    }
+                } else {
+                    let mut chars = key.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(ch), None) => KeyCode::Char(ch),
+                        _ => return Err(format!("无法识别的按键: {key}")),
+                    }
+                }
+            }
+        };
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            f.write_str("Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(ch) if self.modifiers.is_empty() => write!(f, "{ch}"),
+            KeyCode::Char(ch) => write!(f, "{}", ch.to_ascii_uppercase()),
+            KeyCode::F(number) => write!(f, "F{number}"),
+            KeyCode::BackTab => f.write_str("Shift+Tab"),
+            KeyCode::Up => f.write_str("↑"),
+            KeyCode::Down => f.write_str("↓"),
+            KeyCode::Left => f.write_str("←"),
+            KeyCode::Right => f.write_str("→"),
+            KeyCode::PageUp => f.write_str("PageUp"),
+            KeyCode::PageDown => f.write_str("PageDown"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+/// 页面内固定的按键。同一张表既用于查找按键对应的命令，也用于生成帮助窗口。
+#[derive(Debug, Clone, Copy)]
+pub struct FixedKey<C> {
+    pub keys: &'static [&'static str],
+    pub label: &'static str,
+    pub command: C,
+}
+
+impl<C> FixedKey<C> {
+    pub fn bindings(&self) -> impl Iterator<Item = KeyBinding> + '_ {
+        self.keys
+            .iter()
+            .filter_map(|key| KeyBinding::parse(key).ok())
+    }
+
+    /// 帮助窗口中显示的按键，与 `Keymap::keys_text` 写法一致。
+    pub fn keys_text(&self) -> String {
+        self.bindings()
+            .map(|key| key.to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// 搜索页在某些状态下固定占用的按键，配置的快捷键与之相同时给出提示。
+#[derive(Debug, Clone, Copy)]
+pub struct ReservedKey {
+    pub binding: KeyBinding,
+    /// 占用该键的场合，如“输入框”
+    pub scope: &'static str,
+    pub label: &'static str,
+}
+
+/// 把固定按键表转成 `Keymap::from_config` 检查冲突用的列表。
+pub fn reserved_keys<C>(scope: &'static str, table: &[FixedKey<C>]) -> Vec<ReservedKey> {
+    table
+        .iter()
+        .flat_map(|entry| {
+            entry.bindings().map(|binding| ReservedKey {
+                binding,
+                scope,
+                label: entry.label,
+            })
+        })
+        .collect()
+}
+
+/// 在按键表中查找按下的键对应的命令。
+pub fn fixed_command<C: Copy>(table: &[FixedKey<C>], key: &KeyEvent) -> Option<C> {
+    let pressed = KeyBinding::from_event(key);
+    table
+        .iter()
+        .find(|entry| entry.bindings().any(|binding| binding == pressed))
+        .map(|entry| entry.command)
+}
+
+/// 按键到操作的映射，由默认绑定和配置文件中的 `keymap` 合并而成。
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    actions: HashMap<KeyBinding, Action>,
+    bindings: Vec<(Action, Vec<KeyBinding>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_config(&BTreeMap::new(), &[]).0
+    }
+}
+
+impl Keymap {
+    /// 配置中出现的操作替换其默认按键 (空列表表示解除绑定)。
+    /// 返回的提示包括无法识别的操作、按键，以及按键冲突 (含与 `reserved` 中固定按键的冲突)。
+    pub fn from_config(
+        overrides: &BTreeMap<String, Vec<String>>,
+        reserved: &[ReservedKey],
+    ) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        let mut configured: HashMap<Action, Vec<KeyBinding>> = HashMap::new();
+        for (name, keys) in overrides {
+            let Some(action) = Action::from_name(name) else {
+                warnings.push(format!("未知的快捷键操作: {name}"));
+                continue;
+            };
+            let mut parsed = Vec::new();
+            for key in keys {
+                match KeyBinding::parse(key) {
+                    Ok(binding) => parsed.push(binding),
+                    Err(err) => warnings.push(format!("{name}: {err}")),
+                }
+            }
+            configured.insert(action, parsed);
+        }
+
+        let bindings: Vec<(Action, Vec<KeyBinding>)> = Action::ALL
+            .into_iter()
+            .map(|action| {
+                let keys = configured.get(&action).cloned().unwrap_or_else(|| {
+                    action
+                        .default_keys()
+                        .iter()
+                        .filter_map(|key| KeyBinding::parse(key).ok())
+                        .collect()
+                });
+                (action, keys)
+            })
+            .collect();
+
+        // 冲突时配置文件中写明的操作优先，其次按操作顺序
+        let mut actions: HashMap<KeyBinding, Action> = HashMap::new();
+        let mut ordered: Vec<&(Action, Vec<KeyBinding>)> = bindings.iter().collect();
+        ordered.sort_by_key(|(action, _)| !configured.contains_key(action));
+        for (action, keys) in ordered {
+            for key in keys {
+                match actions.get(key) {
+                    Some(existing) if existing != action => warnings.push(format!(
+                        "按键冲突: {key} 同时绑定到 {} 和 {}，使用 {}",
+                        existing.name(),
+                        action.name(),
+                        existing.name()
+                    )),
+                    Some(_) => {}
+                    None => {
+                        actions.insert(*key, *action);
+                    }
+                }
+            }
+        }
+
+        // 默认按键已避开固定按键，只检查配置文件中写明的
+        for (action, keys) in &bindings {
+            if !configured.contains_key(action) {
+                continue;
+            }
+            for key in keys {
+                for fixed in reserved.iter().filter(|fixed| fixed.binding == *key) {
+                    warnings.push(format!(
+                        "按键冲突: {key} 绑定到 {}，与{}的“{}”冲突",
+                        action.name(),
+                        fixed.scope,
+                        fixed.label
+                    ));
+                }
+            }
+        }
+
+        (Self { actions, bindings }, warnings)
+    }
+
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        self.actions.get(&KeyBinding::from_event(key)).copied()
+    }
+
+    /// 实际生效的按键 (冲突中落败的按键不算)。
+    pub fn keys(&self, action: Action) -> Vec<KeyBinding> {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .map(|(_, keys)| {
+                keys.iter()
+                    .filter(|key| self.actions.get(key) == Some(&action))
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn keys_text(&self, action: Action) -> String {
+        self.keys(action)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// 提示文字中引用的按键，未绑定时为“(未绑定)”。
+    pub fn key_hint(&self, action: Action) -> String {
+        match self.keys_text(action) {
+            keys if keys.is_empty() => "(未绑定)".to_string(),
+            keys => keys,
+        }
+    }
+
+    /// 帮助窗口的分组，按 `Action::ALL` 的顺序；未绑定的操作也列出。
+    pub fn help_sections(&self) -> Vec<HelpSection> {
+        let mut sections: Vec<HelpSection> = Vec::new();
+        for action in Action::ALL {
+            let entry = (
+                self.key_hint(action),
+                format!("{} ({})", action.label(), action.name()),
+            );
+            match sections
+                .iter_mut()
+                .find(|section| section.title == action.category())
+            {
+                Some(section) => section.entries.push(entry),
+                None => sections.push(HelpSection::new(action.category(), vec![entry])),
+            }
+        }
+        sections
+    }
+
+    /// 生成提示行，`extra` 可为某些操作附加说明 (如待复习数量)。
+    pub fn tip_line(&self, extra: impl Fn(Action) -> Option<String>) -> String {
+        Action::ALL
+            .into_iter()
+            .filter(|action| action.in_tip())
+            .filter_map(|action| {
+                let keys = self.keys_text(action);
+                if keys.is_empty() {
+                    return None;
+                }
+                Some(match extra(action) {
+                    Some(extra) => format!("{keys} {}({extra})", action.label()),
+                    None => format!("{keys} {}", action.label()),
+                })
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overrides(entries: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+        entries
+            .iter()
+            .map(|(name, keys)| {
+                (
+                    name.to_string(),
+                    keys.iter().map(ToString::to_string).collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn default_keys_do_not_conflict() {
+        let (_, warnings) = Keymap::from_config(&BTreeMap::new(), &[]);
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn configured_action_wins_conflict() {
+        let (keymap, warnings) = Keymap::from_config(&overrides(&[("ai-query", &["Ctrl+S"])]), &[]);
+
+        let ctrl_s = press(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert_eq!(keymap.action(&ctrl_s), Some(Action::AiQuery));
+        assert!(keymap.keys(Action::SaveWord).is_empty());
+        assert_eq!(keymap.key_hint(Action::SaveWord), "(未绑定)");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("ai-query") && warnings[0].contains("save-word"));
+    }
+
+    #[test]
+    fn configured_keys_are_checked_against_reserved_keys() {
+        let table = [FixedKey {
+            keys: &["Ctrl+W", "Esc"],
+            label: "删除光标前的一个词",
+            command: (),
+        }];
+        let reserved = reserved_keys("输入框", &table);
+
+        let (keymap, warnings) =
+            Keymap::from_config(&overrides(&[("ai-query", &["Ctrl+W"])]), &reserved);
+        assert_eq!(
+            keymap.action(&press(KeyCode::Char('w'), KeyModifiers::CONTROL)),
+            Some(Action::AiQuery)
+        );
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("输入框"));
+
+        // 默认按键 (退出的 Esc) 不提示
+        let (_, warnings) = Keymap::from_config(&BTreeMap::new(), &reserved);
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn empty_list_unbinds_action() {
+        let (keymap, warnings) = Keymap::from_config(&overrides(&[("quit", &[])]), &[]);
+        assert!(warnings.is_empty());
+        assert_eq!(
+            keymap.action(&press(KeyCode::Esc, KeyModifiers::NONE)),
+            None
+        );
+    }
+
+    #[test]
+    fn unknown_actions_and_keys_are_reported() {
+        let (keymap, warnings) = Keymap::from_config(
+            &overrides(&[
+                ("no-such-action", &["F9"]),
+                ("ai-query", &["NoSuchKey", "F9"]),
+            ]),
+            &[],
+        );
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert_eq!(
+            keymap.action(&press(KeyCode::F(9), KeyModifiers::NONE)),
+            Some(Action::AiQuery)
+        );
+    }
+
+    #[test]
+    fn char_keys_ignore_shift_and_ctrl_letter_case() {
+        assert_eq!(
+            KeyBinding::from_event(&press(KeyCode::Char('?'), KeyModifiers::SHIFT)),
+            KeyBinding::parse("?").unwrap()
+        );
+        assert_eq!(
+            KeyBinding::from_event(&press(KeyCode::Char('G'), KeyModifiers::CONTROL)),
+            KeyBinding::parse("Ctrl+G").unwrap()
+        );
+        assert_eq!(
+            KeyBinding::parse("Shift+Tab").unwrap().to_string(),
+            "Shift+Tab"
+        );
+    }
+}
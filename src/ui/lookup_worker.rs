@@ -0,0 +1,139 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+
+use anyhow::Result;
+
+use crate::cache::{QueryResultCache, SEARCH_CACHE_CAPACITY};
+use crate::dict::DictionaryStore;
+
+enum LookupRequest {
+    Search { generation: u64, query: String },
+    Definition { entry_idx: usize },
+    Definitions { entries: Vec<usize> },
+}
+
+/// 后台线程完成的搜索或词条读取。
+pub enum LookupResult {
+    Search {
+        generation: u64,
+        indexes: Vec<usize>,
+    },
+    Definition {
+        entry_idx: usize,
+        definition: Result<String>,
+    },
+}
+
+/// 在后台线程搜索和读取词条，大词库中输入或打开长词条时界面不卡顿。
+///
+/// 每次搜索有一个递增的编号，新的搜索开始 (或调用 [`cancel_search`](Self::cancel_search))
+/// 后，进行中的旧搜索在下一次检查时放弃。积压的搜索和 [`load_definition`](Self::load_definition)
+/// 只处理最新的一个，[`load_definitions`](Self::load_definitions) 请求的词条全部读取。
+pub struct LookupWorker {
+    requests: Sender<LookupRequest>,
+    results: Receiver<LookupResult>,
+    generation: Arc<AtomicU64>,
+}
+
+impl LookupWorker {
+    pub fn new(store: DictionaryStore) -> Self {
+        let (request_sender, request_receiver) = mpsc::channel();
+        let (result_sender, result_receiver) = mpsc::channel();
+        let generation = Arc::new(AtomicU64::new(0));
+        let latest = Arc::clone(&generation);
+        // 界面退出时发送端随之释放，线程在 `recv` 出错后结束
+        thread::spawn(move || run_worker(&store, &request_receiver, &result_sender, &latest));
+        Self {
+            requests: request_sender,
+            results: result_receiver,
+            generation,
+        }
+    }
+
+    /// 开始新的搜索，返回它的编号。
+    pub fn search(&self, query: &str) -> u64 {
+        let generation = self.cancel_search();
+        let _ = self.requests.send(LookupRequest::Search {
+            generation,
+            query: query.to_string(),
+        });
+        generation
+    }
+
+    /// 作废进行中和排队中的搜索，返回新的编号。
+    pub fn cancel_search(&self) -> u64 {
+        self.generation.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    pub fn load_definition(&self, entry_idx: usize) {
+        let _ = self.requests.send(LookupRequest::Definition { entry_idx });
+    }
+
+    /// 读取一组词条，每个词条返回一个结果，供复制、保存、AI 查询等需要原文的操作使用。
+    pub fn load_definitions(&self, entries: &[usize]) {
+        if !entries.is_empty() {
+            let _ = self.requests.send(LookupRequest::Definitions {
+                entries: entries.to_vec(),
+            });
+        }
+    }
+
+    /// 取出一个已完成的结果，不等待。
+    pub fn try_recv(&self) -> Option<LookupResult> {
+        self.results.try_recv().ok()
+    }
+}
+
+fn run_worker(
+    store: &DictionaryStore,
+    requests: &Receiver<LookupRequest>,
+    results: &Sender<LookupResult>,
+    latest: &AtomicU64,
+) {
+    let mut result_cache = QueryResultCache::new(SEARCH_CACHE_CAPACITY);
+    while let Ok(first) = requests.recv() {
+        let mut search = None;
+        let mut definition = None;
+        let mut batch = Vec::new();
+        for request in std::iter::once(first).chain(requests.try_iter()) {
+            match request {
+                LookupRequest::Search { generation, query } => search = Some((generation, query)),
+                LookupRequest::Definition { entry_idx } => definition = Some(entry_idx),
+                LookupRequest::Definitions { entries } => batch.extend(entries),
+            }
+        }
+        batch.sort_unstable();
+        batch.dedup();
+        batch.retain(|&entry_idx| Some(entry_idx) != definition);
+
+        // 先读取词条：它对应界面上当前选中的结果或等待中的操作，通常也比搜索快
+        for entry_idx in definition.into_iter().chain(batch) {
+            let definition = store.fetch_definition(entry_idx);
+            let result = LookupResult::Definition {
+                entry_idx,
+                definition,
+            };
+            if results.send(result).is_err() {
+                return;
+            }
+        }
+
+        let Some((generation, query)) = search else {
+            continue;
+        };
+        let cancelled = || latest.load(Ordering::SeqCst) != generation;
+        if let Some(indexes) = result_cache.query(store, &query, &cancelled) {
+            if results
+                .send(LookupResult::Search {
+                    generation,
+                    indexes,
+                })
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+}
@@ -1,5 +1,18 @@
 pub mod search;
 pub mod config_editor;
+pub mod ai_history;
+pub mod ai_session;
+pub mod notebook;
+pub mod review;
+pub mod history;
+pub mod detail_find;
+pub mod keymap;
+pub mod text_edit;
+pub mod detail_text;
+pub mod theme;
+pub mod clipboard;
+pub mod help;
+pub mod lookup_worker;
 
 pub use search::run_search;
 #[allow(unused_imports)]
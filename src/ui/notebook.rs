@@ -0,0 +1,500 @@
+use std::io;
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::text::{Line, Text};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{backend::CrosstermBackend, Frame, Terminal};
+
+use crate::app::time::{format_timestamp, now_secs};
+use crate::app::Config;
+use crate::cache::DefinitionCache;
+use crate::dict::DictionaryStore;
+use crate::export::export_notebook;
+use crate::notebook::store::parse_tags;
+use crate::notebook::{Notebook, NotebookEntry};
+use crate::render::ai_answer::render_answer_body;
+use crate::ui::help::{HelpOverlay, HelpSection};
+use crate::ui::keymap::{fixed_command, FixedKey};
+use crate::ui::theme::Theme;
+
+const PAGE_STEP: usize = 10;
+const HELP_TEXT: &str =
+    "输入过滤 (#标签) | Enter 查词 | Ctrl+E 备注 | Ctrl+T 标签 | Delete 删除 | Ctrl+X 导出 Anki | PageUp/PageDown 滚动 | F1 帮助 | Esc 返回";
+/// 生词本列表的按键，按键处理和帮助窗口共用。
+const NOTEBOOK_KEYS: [FixedKey<NotebookCommand>; 14] = [
+    FixedKey {
+        keys: &["Enter"],
+        label: "在搜索页查询该词",
+        command: NotebookCommand::Open,
+    },
+    FixedKey {
+        keys: &["Delete", "Ctrl+D"],
+        label: "从生词本删除",
+        command: NotebookCommand::Delete,
+    },
+    FixedKey {
+        keys: &["Ctrl+E"],
+        label: "编辑备注",
+        command: NotebookCommand::EditNote,
+    },
+    FixedKey {
+        keys: &["Ctrl+T"],
+        label: "编辑标签",
+        command: NotebookCommand::EditTags,
+    },
+    FixedKey {
+        keys: &["Ctrl+X"],
+        label: "导出为 Anki 卡片",
+        command: NotebookCommand::Export,
+    },
+    FixedKey {
+        keys: &["Up"],
+        label: "上一个单词",
+        command: NotebookCommand::Up,
+    },
+    FixedKey {
+        keys: &["Down"],
+        label: "下一个单词",
+        command: NotebookCommand::Down,
+    },
+    FixedKey {
+        keys: &["Home"],
+        label: "第一个单词",
+        command: NotebookCommand::First,
+    },
+    FixedKey {
+        keys: &["End"],
+        label: "最后一个单词",
+        command: NotebookCommand::Last,
+    },
+    FixedKey {
+        keys: &["PageUp"],
+        label: "向上滚动详情",
+        command: NotebookCommand::ScrollUp,
+    },
+    FixedKey {
+        keys: &["PageDown"],
+        label: "向下滚动详情",
+        command: NotebookCommand::ScrollDown,
+    },
+    FixedKey {
+        keys: &["Backspace"],
+        label: "删除过滤条件的最后一个字符",
+        command: NotebookCommand::DeleteFilterChar,
+    },
+    FixedKey {
+        keys: &["F1"],
+        label: "帮助",
+        command: NotebookCommand::Help,
+    },
+    FixedKey {
+        keys: &["Esc"],
+        label: "返回搜索",
+        command: NotebookCommand::Back,
+    },
+];
+/// 编辑备注或标签时的按键 (`handle_edit_key`)。
+const FIELD_EDIT_KEYS: [FixedKey<FieldEditCommand>; 3] = [
+    FixedKey {
+        keys: &["Enter"],
+        label: "保存",
+        command: FieldEditCommand::Save,
+    },
+    FixedKey {
+        keys: &["Esc"],
+        label: "取消编辑",
+        command: FieldEditCommand::Cancel,
+    },
+    FixedKey {
+        keys: &["Backspace"],
+        label: "删除最后一个字符",
+        command: FieldEditCommand::DeleteChar,
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NotebookCommand {
+    Open,
+    Delete,
+    EditNote,
+    EditTags,
+    Export,
+    Up,
+    Down,
+    First,
+    Last,
+    ScrollUp,
+    ScrollDown,
+    DeleteFilterChar,
+    Help,
+    Back,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldEditCommand {
+    Save,
+    Cancel,
+    DeleteChar,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EditField {
+    Note,
+    Tags,
+}
+
+struct NotebookState {
+    filter: String,
+    matches: Vec<usize>,
+    selected: usize,
+    preview_scroll: usize,
+    editing: Option<(EditField, String)>,
+    status: String,
+    help: Option<HelpOverlay>,
+    theme: Theme,
+    /// 搜索页加入生词本的按键，用于空列表的提示
+    save_keys: String,
+}
+
+impl NotebookState {
+    fn new(notebook: &Notebook, theme: Theme, save_keys: String) -> Self {
+        let mut state = Self {
+            filter: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+            preview_scroll: 0,
+            editing: None,
+            status: HELP_TEXT.to_string(),
+            help: None,
+            theme,
+            save_keys,
+        };
+        state.refresh(notebook);
+        state
+    }
+
+    fn refresh(&mut self, notebook: &Notebook) {
+        self.matches = notebook.search(&self.filter);
+        self.selected = self.selected.min(self.matches.len().saturating_sub(1));
+        self.preview_scroll = 0;
+    }
+
+    fn selected_entry_index(&self) -> Option<usize> {
+        self.matches.get(self.selected).copied()
+    }
+
+    fn select(&mut self, selected: usize) {
+        self.selected = selected.min(self.matches.len().saturating_sub(1));
+        self.preview_scroll = 0;
+    }
+
+    fn begin_edit(&mut self, notebook: &Notebook, field: EditField) {
+        let Some(entry) = self
+            .selected_entry_index()
+            .and_then(|idx| notebook.entries().get(idx))
+        else {
+            return;
+        };
+        let value = match field {
+            EditField::Note => entry.note.clone(),
+            EditField::Tags => entry.tags.join(", "),
+        };
+        self.editing = Some((field, value));
+    }
+}
+
+/// 浏览和整理生词本。返回用户选择回到搜索页查询的单词，Esc 返回 `None`。
+pub fn run_notebook(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    notebook: &mut Notebook,
+    store: &DictionaryStore,
+    definition_cache: &mut DefinitionCache,
+    config: &Config,
+    save_keys: String,
+) -> Result<Option<String>> {
+    let mut state = NotebookState::new(notebook, Theme::from_config(config).0, save_keys);
+
+    loop {
+        terminal.draw(|frame| draw_notebook_ui(frame, notebook, &state))?;
+
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        if let Some(help) = state.help.as_mut() {
+            if help.handle_key(&key) {
+                state.help = None;
+            }
+            continue;
+        }
+        if state.editing.is_some() {
+            handle_edit_key(&mut state, notebook, &key);
+            continue;
+        }
+
+        let Some(command) = fixed_command(&NOTEBOOK_KEYS, &key) else {
+            if let KeyCode::Char(ch) = key.code {
+                if !ch.is_control() && !key.modifiers.contains(KeyModifiers::CONTROL) {
+                    state.filter.push(ch);
+                    state.selected = 0;
+                    state.refresh(notebook);
+                }
+            }
+            continue;
+        };
+        match command {
+            NotebookCommand::Back => return Ok(None),
+            NotebookCommand::Open => {
+                if let Some(entry) = state
+                    .selected_entry_index()
+                    .and_then(|idx| notebook.entries().get(idx))
+                {
+                    return Ok(Some(entry.word.clone()));
+                }
+            }
+            NotebookCommand::Delete => delete_selected(&mut state, notebook),
+            NotebookCommand::EditNote => state.begin_edit(notebook, EditField::Note),
+            NotebookCommand::EditTags => state.begin_edit(notebook, EditField::Tags),
+            NotebookCommand::Export => {
+                state.status = "正在导出 Anki 卡片...".to_string();
+                terminal.draw(|frame| draw_notebook_ui(frame, notebook, &state))?;
+                state.status = export_anki(notebook, store, definition_cache, config);
+            }
+            NotebookCommand::Up => state.select(state.selected.saturating_sub(1)),
+            NotebookCommand::Down => state.select(state.selected + 1),
+            NotebookCommand::First => state.select(0),
+            NotebookCommand::Last => state.select(state.matches.len().saturating_sub(1)),
+            NotebookCommand::ScrollUp => {
+                state.preview_scroll = state.preview_scroll.saturating_sub(PAGE_STEP);
+            }
+            NotebookCommand::ScrollDown => {
+                state.preview_scroll = state.preview_scroll.saturating_add(PAGE_STEP);
+            }
+            NotebookCommand::DeleteFilterChar => {
+                state.filter.pop();
+                state.selected = 0;
+                state.refresh(notebook);
+            }
+            NotebookCommand::Help => state.help = Some(notebook_help()),
+        }
+    }
+}
+
+fn notebook_help() -> HelpOverlay {
+    HelpOverlay::new(
+        "生词本快捷键",
+        vec![
+            HelpSection::from_keys(
+                "生词列表",
+                &NOTEBOOK_KEYS,
+                &[("字符", "输入过滤条件，#标签 按标签过滤")],
+            ),
+            HelpSection::from_keys("编辑备注/标签", &FIELD_EDIT_KEYS, &[("字符", "输入")]),
+        ],
+    )
+}
+
+fn handle_edit_key(state: &mut NotebookState, notebook: &mut Notebook, key: &KeyEvent) {
+    let Some((field, buffer)) = state.editing.as_mut() else {
+        return;
+    };
+    match fixed_command(&FIELD_EDIT_KEYS, key) {
+        Some(FieldEditCommand::Cancel) => {
+            state.editing = None;
+            state.status = "已取消编辑。".to_string();
+        }
+        Some(FieldEditCommand::Save) => {
+            let (field, value) = (*field, std::mem::take(buffer));
+            state.editing = None;
+            commit_edit(state, notebook, field, value);
+        }
+        Some(FieldEditCommand::DeleteChar) => {
+            buffer.pop();
+        }
+        None => {
+            if let KeyCode::Char(ch) = key.code {
+                if !ch.is_control() {
+                    buffer.push(ch);
+                }
+            }
+        }
+    }
+}
+
+fn commit_edit(
+    state: &mut NotebookState,
+    notebook: &mut Notebook,
+    field: EditField,
+    value: String,
+) {
+    let Some(entry) = state
+        .selected_entry_index()
+        .and_then(|idx| notebook.get_mut(idx))
+    else {
+        return;
+    };
+    match field {
+        EditField::Note => entry.note = value.trim().to_string(),
+        EditField::Tags => entry.tags = parse_tags(&value),
+    }
+    entry.updated_at = now_secs();
+    let word = entry.word.clone();
+
+    state.status = match notebook.save() {
+        Ok(()) => format!("已更新: {word}"),
+        Err(err) => format!("保存生词本失败: {err}"),
+    };
+    let selected = state.selected_entry_index();
+    state.refresh(notebook);
+    if let Some(pos) = state.matches.iter().position(|&idx| Some(idx) == selected) {
+        state.selected = pos;
+    }
+}
+
+fn export_anki(
+    notebook: &Notebook,
+    store: &DictionaryStore,
+    definition_cache: &mut DefinitionCache,
+    config: &Config,
+) -> String {
+    match export_notebook(notebook, store, definition_cache, config.anki_html_back) {
+        Ok(export) => export.summary(),
+        Err(err) => format!("导出 Anki 失败: {err}"),
+    }
+}
+
+fn delete_selected(state: &mut NotebookState, notebook: &mut Notebook) {
+    let Some(idx) = state.selected_entry_index() else {
+        return;
+    };
+    let Some(entry) = notebook.remove(idx) else {
+        return;
+    };
+
+    state.status = match notebook.save() {
+        Ok(()) => format!("已从生词本删除: {}", entry.word),
+        Err(err) => format!("删除后保存生词本失败: {err}"),
+    };
+    state.refresh(notebook);
+}
+
+fn draw_notebook_ui(frame: &mut Frame, notebook: &Notebook, state: &NotebookState) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ])
+        .split(frame.area());
+
+    let input = match &state.editing {
+        Some((field, buffer)) => {
+            let title = match field {
+                EditField::Note => "编辑备注 (Enter 保存，Esc 取消)",
+                EditField::Tags => "编辑标签，逗号或空格分隔 (Enter 保存，Esc 取消)",
+            };
+            Paragraph::new(buffer.as_str()).block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(state.theme.border_editing),
+            )
+        }
+        None => Paragraph::new(state.filter.as_str()).block(
+            Block::default()
+                .title(format!("生词本 (共 {} 条)", notebook.entries().len()))
+                .borders(Borders::ALL),
+        ),
+    };
+    frame.render_widget(input, rows[0]);
+    frame.render_widget(Paragraph::new(state.status.as_str()), rows[1]);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(rows[2]);
+
+    let list_items: Vec<ListItem> = if state.matches.is_empty() {
+        vec![ListItem::new(format!(
+            "生词本为空，在搜索页按 {} 保存当前词条",
+            state.save_keys
+        ))]
+    } else {
+        state
+            .matches
+            .iter()
+            .map(|idx| {
+                let entry = &notebook.entries()[*idx];
+                let tags: String = entry.tags.iter().map(|tag| format!(" #{tag}")).collect();
+                ListItem::new(format!("{}  [{}]{}", entry.word, entry.source, tags))
+            })
+            .collect()
+    };
+
+    let list = List::new(list_items)
+        .block(Block::default().title("已保存单词").borders(Borders::ALL))
+        .highlight_style(state.theme.highlight)
+        .highlight_symbol(">> ");
+
+    let mut list_state = ListState::default();
+    if !state.matches.is_empty() {
+        list_state.select(Some(state.selected));
+    }
+    frame.render_stateful_widget(list, columns[0], &mut list_state);
+
+    let preview_text = state
+        .selected_entry_index()
+        .and_then(|idx| notebook.entries().get(idx))
+        .map(|entry| build_preview(entry, &state.theme))
+        .unwrap_or_default();
+    let scroll = state.preview_scroll.min(u16::MAX as usize) as u16;
+    let preview = Paragraph::new(preview_text)
+        .block(Block::default().title("详情").borders(Borders::ALL))
+        .scroll((scroll, 0))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(preview, columns[1]);
+    if let Some(help) = &state.help {
+        help.draw(frame, &state.theme);
+    }
+}
+
+fn build_preview(entry: &NotebookEntry, theme: &Theme) -> Text<'static> {
+    let heading = theme.headword;
+    let mut lines = vec![
+        Line::styled(format!("{} [{}]", entry.word, entry.source), heading),
+        Line::from(format!("添加于 {}", format_timestamp(entry.created_at))),
+    ];
+    if !entry.tags.is_empty() {
+        lines.push(Line::from(format!("标签: {}", entry.tags.join(", "))));
+    }
+    if !entry.note.is_empty() {
+        lines.push(Line::from(format!("备注: {}", entry.note)));
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::styled("词典释义", heading));
+    lines.extend(
+        entry
+            .definition
+            .lines()
+            .map(|line| Line::from(line.to_string())),
+    );
+
+    if let Some(answer) = &entry.ai_answer {
+        lines.push(Line::default());
+        lines.push(Line::styled("AI 回答", heading));
+        lines.extend(render_answer_body(answer, theme));
+    }
+    Text::from(lines)
+}
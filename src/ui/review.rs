@@ -0,0 +1,323 @@
+use std::collections::VecDeque;
+use std::io;
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyEventKind};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Text};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::{backend::CrosstermBackend, Frame, Terminal};
+
+use crate::app::time::{end_of_today, now_secs};
+use crate::cache::DefinitionCache;
+use crate::dict::DictionaryStore;
+use crate::notebook::{Grade, Notebook, NotebookEntry};
+use crate::render::html_to_plain_text;
+use crate::ui::help::{HelpOverlay, HelpSection};
+use crate::ui::keymap::{fixed_command, FixedKey};
+use crate::ui::theme::Theme;
+
+const SCROLL_STEP: usize = 3;
+/// 复习页的按键，按键处理和帮助窗口共用。评分键只在揭晓释义后生效。
+const REVIEW_KEYS: [FixedKey<ReviewCommand>; 9] = [
+    FixedKey {
+        keys: &["Space", "Enter"],
+        label: "揭晓释义",
+        command: ReviewCommand::Reveal,
+    },
+    FixedKey {
+        keys: &["1"],
+        label: "评分：忘记 (本轮稍后再复习一次)",
+        command: ReviewCommand::Grade(Grade::Again),
+    },
+    FixedKey {
+        keys: &["2"],
+        label: "评分：困难",
+        command: ReviewCommand::Grade(Grade::Hard),
+    },
+    FixedKey {
+        keys: &["3"],
+        label: "评分：良好",
+        command: ReviewCommand::Grade(Grade::Good),
+    },
+    FixedKey {
+        keys: &["4"],
+        label: "评分：简单",
+        command: ReviewCommand::Grade(Grade::Easy),
+    },
+    FixedKey {
+        keys: &["Up"],
+        label: "向上滚动释义",
+        command: ReviewCommand::ScrollUp,
+    },
+    FixedKey {
+        keys: &["Down"],
+        label: "向下滚动释义",
+        command: ReviewCommand::ScrollDown,
+    },
+    FixedKey {
+        keys: &["F1", "?"],
+        label: "帮助",
+        command: ReviewCommand::Help,
+    },
+    FixedKey {
+        keys: &["Esc"],
+        label: "结束复习",
+        command: ReviewCommand::Back,
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReviewCommand {
+    Reveal,
+    Grade(Grade),
+    ScrollUp,
+    ScrollDown,
+    Help,
+    Back,
+}
+
+struct ReviewSession {
+    queue: VecDeque<usize>,
+    revealed: bool,
+    answer: Text<'static>,
+    scroll: usize,
+    reviewed: usize,
+    forgotten: usize,
+    status: String,
+    help: Option<HelpOverlay>,
+    theme: Theme,
+    /// 搜索页加入生词本的按键，用于没有待复习单词时的提示
+    save_keys: String,
+}
+
+impl ReviewSession {
+    fn current(&self) -> Option<usize> {
+        self.queue.front().copied()
+    }
+}
+
+/// 复习今天到期的生词：先显示词头，揭晓释义后自评，按 SM-2 重新安排。
+pub fn run_review(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    notebook: &mut Notebook,
+    store: &DictionaryStore,
+    definition_cache: &mut DefinitionCache,
+    theme: &Theme,
+    save_keys: String,
+) -> Result<()> {
+    let mut session = ReviewSession {
+        queue: notebook.due_indexes(end_of_today()).into(),
+        revealed: false,
+        answer: Text::default(),
+        scroll: 0,
+        reviewed: 0,
+        forgotten: 0,
+        status: String::new(),
+        help: None,
+        theme: theme.clone(),
+        save_keys,
+    };
+
+    loop {
+        terminal.draw(|frame| draw_review_ui(frame, notebook, &session))?;
+
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        if let Some(help) = session.help.as_mut() {
+            if help.handle_key(&key) {
+                session.help = None;
+            }
+            continue;
+        }
+        let Some(idx) = session.current() else {
+            return Ok(());
+        };
+
+        let Some(command) = fixed_command(&REVIEW_KEYS, &key) else {
+            continue;
+        };
+        match command {
+            ReviewCommand::Back => return Ok(()),
+            ReviewCommand::Reveal if !session.revealed => {
+                let entry = &notebook.entries()[idx];
+                session.answer = build_answer(entry, store, definition_cache, &session.theme);
+                session.revealed = true;
+                session.scroll = 0;
+            }
+            ReviewCommand::Grade(grade) if session.revealed => {
+                grade_current(&mut session, notebook, idx, grade);
+            }
+            ReviewCommand::ScrollUp => {
+                session.scroll = session.scroll.saturating_sub(SCROLL_STEP);
+            }
+            ReviewCommand::ScrollDown => {
+                session.scroll = session.scroll.saturating_add(SCROLL_STEP);
+            }
+            ReviewCommand::Help => {
+                session.help = Some(HelpOverlay::new(
+                    "复习页快捷键",
+                    vec![HelpSection::from_keys("生词复习", &REVIEW_KEYS, &[])],
+                ));
+            }
+            ReviewCommand::Reveal | ReviewCommand::Grade(_) => {}
+        }
+    }
+}
+
+fn grade_current(session: &mut ReviewSession, notebook: &mut Notebook, idx: usize, grade: Grade) {
+    let Some(entry) = notebook.get_mut(idx) else {
+        return;
+    };
+    entry.review.schedule(grade, now_secs());
+    let word = entry.word.clone();
+    let interval = entry.review.interval_days;
+
+    session.queue.pop_front();
+    session.reviewed += 1;
+    if grade == Grade::Again {
+        // 忘记的单词在本轮末尾再出现一次
+        session.forgotten += 1;
+        session.queue.push_back(idx);
+    }
+    session.revealed = false;
+    session.scroll = 0;
+    session.status = match notebook.save() {
+        Ok(()) => format!("{word}: {}，{interval} 天后复习", grade.label()),
+        Err(err) => format!("保存复习进度失败: {err}"),
+    };
+}
+
+/// 优先从词典重新读取释义，词典中找不到时使用保存时的释义。
+fn build_answer(
+    entry: &NotebookEntry,
+    store: &DictionaryStore,
+    definition_cache: &mut DefinitionCache,
+    theme: &Theme,
+) -> Text<'static> {
+    let definition = store
+        .find_exact(&entry.word, Some(&entry.source))
+        .and_then(|idx| definition_cache.get_or_load(store, idx).ok())
+        .map(|raw| html_to_plain_text(&raw))
+        .filter(|text| !text.trim().is_empty())
+        .unwrap_or_else(|| entry.definition.clone());
+
+    let mut lines = Vec::new();
+    if !entry.note.is_empty() {
+        lines.push(Line::styled(format!("备注: {}", entry.note), theme.accent));
+        lines.push(Line::default());
+    }
+    lines.extend(definition.lines().map(|line| Line::from(line.to_string())));
+    Text::from(lines)
+}
+
+fn draw_review_ui(frame: &mut Frame, notebook: &Notebook, session: &ReviewSession) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(frame.area());
+
+    let header = format!(
+        "剩余 {} | 已复习 {} | 忘记 {}",
+        session.queue.len(),
+        session.reviewed,
+        session.forgotten
+    );
+    frame.render_widget(
+        Paragraph::new(header).block(Block::default().title("生词复习").borders(Borders::ALL)),
+        rows[0],
+    );
+
+    let Some(entry) = session
+        .current()
+        .and_then(|idx| notebook.entries().get(idx))
+    else {
+        let done = if session.reviewed == 0 {
+            format!(
+                "今天没有需要复习的单词。在搜索页按 {} 把单词加入生词本。",
+                session.save_keys
+            )
+        } else {
+            "本轮复习完成！".to_string()
+        };
+        frame.render_widget(
+            Paragraph::new(done).block(Block::default().borders(Borders::ALL)),
+            rows[1],
+        );
+        frame.render_widget(Paragraph::new(session.status.as_str()), rows[2]);
+        frame.render_widget(Paragraph::new("按任意键返回"), rows[3]);
+        return;
+    };
+
+    let mut lines = vec![
+        Line::styled(entry.word.clone(), session.theme.headword),
+        Line::styled(
+            format!("[{}]", entry.source),
+            Style::default().add_modifier(Modifier::DIM),
+        ),
+        Line::default(),
+    ];
+    if session.revealed {
+        lines.extend(session.answer.lines.iter().cloned());
+    } else {
+        lines.push(Line::from("回忆这个词的意思，然后按 Space 揭晓释义。"));
+    }
+    let scroll = session.scroll.min(u16::MAX as usize) as u16;
+    frame.render_widget(
+        Paragraph::new(Text::from(lines))
+            .block(Block::default().borders(Borders::ALL))
+            .scroll((scroll, 0))
+            .wrap(Wrap { trim: false }),
+        rows[1],
+    );
+
+    frame.render_widget(Paragraph::new(session.status.as_str()), rows[2]);
+    let keys = if session.revealed {
+        grade_hints(entry)
+    } else {
+        "Space/Enter 揭晓释义 | F1 帮助 | Esc 结束复习".to_string()
+    };
+    frame.render_widget(Paragraph::new(keys), rows[3]);
+    if let Some(help) = &session.help {
+        help.draw(frame, &session.theme);
+    }
+}
+
+/// 每个评分键后附上按该评分安排的下次复习间隔。
+fn grade_hints(entry: &NotebookEntry) -> String {
+    let now = now_secs();
+    let mut hints: Vec<String> = Grade::ALL
+        .iter()
+        .enumerate()
+        .map(|(idx, grade)| {
+            let mut preview = entry.review.clone();
+            preview.schedule(*grade, now);
+            format!(
+                "{} {} ({} 天)",
+                idx + 1,
+                grade.label(),
+                preview.interval_days
+            )
+        })
+        .collect();
+    hints.push("↑/↓ 滚动".to_string());
+    hints.push("F1 帮助".to_string());
+    hints.push("Esc 结束复习".to_string());
+    hints.join(" | ")
+}
@@ -1,69 +1,243 @@
+use std::collections::HashMap;
 use std::io;
-use std::sync::mpsc::{self, Receiver, TryRecvError};
-use std::time::Duration;
+use std::mem;
+use std::time::{Duration, Instant};
 
-use anyhow::{bail, Context, Result};
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use anyhow::{Context, Result};
+use crossterm::event::{
+    self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use crossterm::execute;
 use crossterm::terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
-    style::{Modifier, Style},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    layout::{Constraint, Direction, Layout, Margin, Position, Rect},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Tabs, Wrap},
     Frame, Terminal,
 };
 
-use crate::ai::AiClient;
-use crate::app::Config;
-use crate::cache::{
-    DefinitionCache, QueryResultCache, DEFINITION_CACHE_CAPACITY, SEARCH_CACHE_CAPACITY,
-};
+use crate::ai::prompt::{render_template, PromptVars};
+use crate::ai::translate::{difficult_words, is_sentence, word_forms};
+use crate::app::time::end_of_today;
+use crate::app::{Config, LayoutMode};
+use crate::cache::{DefinitionCache, DEFINITION_CACHE_CAPACITY};
 use crate::dict::DictionaryStore;
+use crate::history::{LookupHistory, LookupKind};
+use crate::notebook::{Notebook, NotebookEntry};
+use crate::render::ai_answer::text_to_plain;
+use crate::render::html::{cross_references, CrossRef};
 use crate::render::{build_preview_html_file, html_to_plain_text, open_in_browser};
+use crate::ui::ai_history::run_ai_history;
+use crate::ui::ai_session::{AiRequest, AiSession, AiUpdate};
+use crate::ui::clipboard::copy_to_clipboard;
 use crate::ui::config_editor::run_config_editor;
+use crate::ui::detail_find::DetailFind;
+use crate::ui::detail_text::{entry_text, link_at};
+use crate::ui::help::{HelpOverlay, HelpSection};
+use crate::ui::history::run_history;
+use crate::ui::keymap::{fixed_command, reserved_keys, Action, FixedKey, Keymap};
+use crate::ui::lookup_worker::{LookupResult, LookupWorker};
+use crate::ui::notebook::run_notebook;
+use crate::ui::review::run_review;
+use crate::ui::text_edit::{single_line_view, LineEditor, EDIT_KEYS, EDIT_NOTES};
+use crate::ui::theme::Theme;
 
 const PAGE_STEP: usize = 10;
 const DETAIL_SCROLL_STEP: usize = 3;
-const AI_SPINNER: &[&str] = &["-", "\\", "|", "/"];
+const MAX_PROMPT_DEFINITION_CHARS: usize = 4000;
+const MAX_GLOSSARY_WORDS: usize = 12;
+const GLOSSARY_SNIPPET_CHARS: usize = 200;
+/// 跳转到匹配时在其上方保留的行数。
+const FIND_CONTEXT_ROWS: usize = 2;
+const MIN_LIST_PERCENT: u16 = 15;
+const MAX_LIST_PERCENT: u16 = 85;
+const MAX_TABS: usize = 9;
+/// 标签栏中每个标签页标题最多显示的字符数。
+const TAB_TITLE_CHARS: usize = 16;
+const LIST_PERCENT_STEP: u16 = 5;
+/// 输入停顿这么久后才开始搜索，连续输入时只搜索最后的内容。
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(80);
+/// 等待后台结果时的刷新间隔，也是加载动画的帧间隔。
+const BUSY_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const LOOKUP_SPINNER: &[&str] = &["-", "\\", "|", "/"];
+/// 输入查找内容时的按键 (`handle_find_edit_key`)。
+const FIND_EDIT_KEYS: [FixedKey<FindCommand>; 3] = [
+    FixedKey {
+        keys: &["Enter"],
+        label: "确定查找内容，之后可以跳转",
+        command: FindCommand::Confirm,
+    },
+    FixedKey {
+        keys: &["Backspace"],
+        label: "删除查找内容的最后一个字符",
+        command: FindCommand::DeleteChar,
+    },
+    FixedKey {
+        keys: &["Esc"],
+        label: "结束查找",
+        command: FindCommand::Close,
+    },
+];
+/// 确定查找内容后的跳转键 (`handle_find_key`)。
+const FIND_KEYS: [FixedKey<FindCommand>; 3] = [
+    FixedKey {
+        keys: &["n", "Enter"],
+        label: "下一个匹配",
+        command: FindCommand::Next,
+    },
+    FixedKey {
+        keys: &["N"],
+        label: "上一个匹配",
+        command: FindCommand::Prev,
+    },
+    FixedKey {
+        keys: &["Esc"],
+        label: "结束查找",
+        command: FindCommand::Close,
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FindCommand {
+    Confirm,
+    DeleteChar,
+    Next,
+    Prev,
+    Close,
+}
+
+/// 搜索页的鼠标操作，`handle_mouse` 按它分派，帮助窗口按 `ALL` 列出。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MouseAction {
+    SelectRow,
+    Scroll,
+    ResizeSplit,
+    FollowLink,
+}
+
+impl MouseAction {
+    const ALL: [MouseAction; 4] = [
+        MouseAction::SelectRow,
+        MouseAction::Scroll,
+        MouseAction::ResizeSplit,
+        MouseAction::FollowLink,
+    ];
+
+    fn trigger(self) -> &'static str {
+        match self {
+            MouseAction::SelectRow => "点击结果行",
+            MouseAction::Scroll => "滚轮",
+            MouseAction::ResizeSplit => "拖动两栏之间的边框",
+            MouseAction::FollowLink => "点击带下划线的链接",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            MouseAction::SelectRow => "选中该词条",
+            MouseAction::Scroll => "在结果列表上切换词条，在详情栏上滚动",
+            MouseAction::ResizeSplit => "调整结果列表宽度",
+            MouseAction::FollowLink => "跳转查询被引用的词条",
+        }
+    }
+}
 
-type AiTaskResult = std::result::Result<String, String>;
+/// 需要词条原文的操作。原文不在缓存中时先交给后台线程读取，全部读到后由 `run_search` 执行，
+/// 读取期间界面照常响应。
+#[derive(Debug)]
+enum EntryAction {
+    CopyDefinition(usize),
+    SaveWord(usize),
+    OpenBrowser(usize),
+    AiQuery(PendingAiQuery),
+}
 
+impl EntryAction {
+    fn entries(&self) -> Vec<usize> {
+        match self {
+            EntryAction::CopyDefinition(idx)
+            | EntryAction::SaveWord(idx)
+            | EntryAction::OpenBrowser(idx) => vec![*idx],
+            EntryAction::AiQuery(query) => query
+                .entry_idx
+                .into_iter()
+                .chain(query.glossary.iter().map(|(_, idx)| *idx))
+                .collect(),
+        }
+    }
+}
+
+/// 发起时记下的 AI 查询，读到引用的词条后再发送。
+#[derive(Debug)]
 struct PendingAiQuery {
     query: String,
-    context: String,
-    receiver: Receiver<AiTaskResult>,
-    handle: tokio::task::JoinHandle<()>,
-    tick: usize,
-}
-
-enum AiPollOutcome {
-    Pending(String),
-    Complete {
-        query: String,
-        context: String,
-        result: AiTaskResult,
-    },
-    Disconnected(String),
+    template: usize,
+    refresh: bool,
+    /// 单词查询引用的选中词条
+    entry_idx: Option<usize>,
+    /// 句子翻译中回查到的难词及其词条
+    glossary: Vec<(String, usize)>,
+}
+
+/// 读到的词条原文，读取失败时为错误信息。
+type LoadedDefinitions = HashMap<usize, Result<String, String>>;
+
+#[derive(Debug)]
+struct WaitingEntryAction {
+    action: EntryAction,
+    definitions: LoadedDefinitions,
+    /// 还在后台读取的词条
+    waiting: Vec<usize>,
 }
 
 #[derive(Debug)]
 struct SearchState {
-    query: String,
+    query: LineEditor,
     result_indexes: Vec<usize>,
     selected: usize,
     detail_text: String,
+    detail_styled: Option<Text<'static>>,
     detail_entry_idx: Option<usize>,
+    /// 详情是词典词条 (而不是提示或错误)，按主题标注词头、音标等
+    detail_is_entry: bool,
+    /// 当前词条中可点击跳转的交叉引用
+    detail_links: Vec<CrossRef>,
     detail_scroll: usize,
+    /// 按详情栏宽度折行后的行数
     detail_line_count: usize,
+    detail_width: u16,
+    find: Option<DetailFind>,
     status_text: String,
+    /// 本标签页进行中的 AI 查询进度，每轮循环刷新
+    ai_status: Option<String>,
+    active_template: usize,
+    template_picker: Option<usize>,
+    /// 正在拖动结果列表与详情栏之间的边框
+    dragging_split: bool,
+    /// 输入停顿到该时刻后开始搜索
+    search_due: Option<Instant>,
+    /// 已交给后台线程、尚未返回的搜索编号
+    search_pending: Option<u64>,
+    /// 正在后台读取的词条
+    definition_pending: Option<usize>,
+    /// 等待词条原文的复制、保存等操作
+    entry_action: Option<WaitingEntryAction>,
+    spinner_tick: usize,
+    /// 离开标签页时 AI 会话显示的结果槽，切回时恢复
+    ai_focus: Option<String>,
+    /// 标签页编号，AI 查询按编号归属发起的标签页
+    tab_id: usize,
+    theme: Theme,
+    help: Option<HelpOverlay>,
 }
 
 impl SearchState {
-    fn update_results(&mut self, dict: &DictionaryStore, result_cache: &mut QueryResultCache) {
-        self.result_indexes = result_cache.query(dict, &self.query);
+    fn set_results(&mut self, indexes: Vec<usize>) {
+        self.result_indexes = indexes;
         self.selected = if self.result_indexes.is_empty() {
             0
         } else {
@@ -73,40 +247,181 @@ impl SearchState {
         self.detail_scroll = 0;
     }
 
+    /// 去掉 `@名称` 词典过滤前缀后的查询内容。
+    fn query_word(&self) -> &str {
+        DictionaryStore::split_source_filter(self.query.text())
+            .1
+            .trim()
+    }
+
+    /// 输入后等待搜索结果期间为 `true`，此时的结果列表仍对应旧的输入。
+    fn searching(&self) -> bool {
+        self.search_due.is_some() || self.search_pending.is_some()
+    }
+
+    /// 切回标签页时重新发起离开时未完成的搜索或词条读取，后台线程只为当前标签页返回结果；
+    /// 等待中的操作需要的词条可能已由其他标签页读进缓存。
+    fn resume_lookup(
+        &mut self,
+        dict: &DictionaryStore,
+        definition_cache: &mut DefinitionCache,
+        worker: &LookupWorker,
+    ) {
+        if self.search_pending.take().is_some() {
+            self.search_due = Some(Instant::now());
+        } else if self.definition_pending.take().is_some() {
+            self.refresh_detail(dict, definition_cache, worker);
+        }
+        if let Some(pending) = self.entry_action.as_mut() {
+            pending
+                .waiting
+                .retain(|&idx| match definition_cache.get(dict, idx) {
+                    Some(definition) => {
+                        pending.definitions.insert(idx, Ok(definition));
+                        false
+                    }
+                    None => true,
+                });
+            worker.load_definitions(&pending.waiting);
+        }
+    }
+
+    fn lookup_busy(&self) -> bool {
+        self.searching() || self.loading_definition()
+    }
+
+    fn loading_definition(&self) -> bool {
+        self.definition_pending.is_some()
+            || self
+                .entry_action
+                .as_ref()
+                .is_some_and(|pending| !pending.waiting.is_empty())
+    }
+
+    /// 开始需要词条原文的操作，取代尚未完成的上一个操作。需要的词条都在缓存中时，
+    /// 操作在下一轮循环立即执行。
+    fn request_entry_action(
+        &mut self,
+        action: EntryAction,
+        dict: &DictionaryStore,
+        definition_cache: &DefinitionCache,
+        worker: &LookupWorker,
+    ) {
+        let mut definitions = LoadedDefinitions::new();
+        let mut waiting = Vec::new();
+        for idx in action.entries() {
+            match definition_cache.get(dict, idx) {
+                Some(definition) => {
+                    definitions.insert(idx, Ok(definition));
+                }
+                None => waiting.push(idx),
+            }
+        }
+        if !waiting.is_empty() {
+            worker.load_definitions(&waiting);
+            self.status_text = "正在读取词条...".to_string();
+        }
+        self.entry_action = Some(WaitingEntryAction {
+            action,
+            definitions,
+            waiting,
+        });
+    }
+
+    /// 对选中词条开始需要原文的操作；没有选中词条时显示 `missing`。
+    fn request_selected_entry(
+        &mut self,
+        action: fn(usize) -> EntryAction,
+        missing: &str,
+        dict: &DictionaryStore,
+        definition_cache: &DefinitionCache,
+        worker: &LookupWorker,
+    ) {
+        match self.selected_entry_index() {
+            Some(idx) => self.request_entry_action(action(idx), dict, definition_cache, worker),
+            None => self.status_text = missing.to_string(),
+        }
+    }
+
+    /// 后台线程读到的词条交给等待中的操作。
+    fn entry_loaded(&mut self, entry_idx: usize, definition: &Result<String>) {
+        let Some(pending) = self.entry_action.as_mut() else {
+            return;
+        };
+        if let Some(pos) = pending.waiting.iter().position(|&idx| idx == entry_idx) {
+            pending.waiting.swap_remove(pos);
+            let definition = match definition {
+                Ok(definition) => Ok(definition.clone()),
+                Err(err) => Err(format!("{err:#}")),
+            };
+            pending.definitions.insert(entry_idx, definition);
+        }
+    }
+
+    /// 取出需要的词条都已读到的操作。
+    fn take_ready_entry_action(&mut self) -> Option<(EntryAction, LoadedDefinitions)> {
+        if !self.entry_action.as_ref()?.waiting.is_empty() {
+            return None;
+        }
+        let pending = self.entry_action.take()?;
+        Some((pending.action, pending.definitions))
+    }
+
     fn selected_entry_index(&self) -> Option<usize> {
-        if self.result_indexes.is_empty() {
+        if self.searching() || self.result_indexes.is_empty() {
             None
         } else {
             Some(self.result_indexes[self.selected])
         }
     }
 
+    /// 显示选中的词条；不在缓存中时交给后台线程读取，结果由 `apply_lookup_result` 显示。
     fn refresh_detail(
         &mut self,
-        dict: &mut DictionaryStore,
+        dict: &DictionaryStore,
         definition_cache: &mut DefinitionCache,
+        worker: &LookupWorker,
     ) {
-        if self.query.trim().is_empty() {
-            self.detail_text = "开始输入关键词后，会在每次输入/删除字符时自动查询。".to_string();
+        if self.query_word().is_empty() {
+            self.definition_pending = None;
+            self.set_detail_text("开始输入关键词后，会在每次输入/删除字符时自动查询。".to_string());
             self.detail_entry_idx = None;
-            self.detail_scroll = 0;
-            self.detail_line_count = count_lines(&self.detail_text);
+            return;
+        }
+        if self.searching() {
             return;
         }
 
         let Some(entry_idx) = self.selected_entry_index() else {
-            self.detail_text = "未找到匹配词条，请尝试修改或缩短关键词。".to_string();
+            self.definition_pending = None;
+            self.set_detail_text("未找到匹配词条，请尝试修改或缩短关键词。".to_string());
             self.detail_entry_idx = None;
-            self.detail_scroll = 0;
-            self.detail_line_count = count_lines(&self.detail_text);
             return;
         };
 
-        if self.detail_entry_idx == Some(entry_idx) {
+        if self.detail_entry_idx == Some(entry_idx) || self.definition_pending == Some(entry_idx) {
             return;
         }
 
-        match definition_cache.get_or_load(dict, entry_idx) {
+        match definition_cache.get(dict, entry_idx) {
+            Some(definition) => self.show_definition(dict, entry_idx, Ok(definition)),
+            None => {
+                worker.load_definition(entry_idx);
+                self.set_detail_text(format!("正在读取 {} ...", dict.entries[entry_idx].word));
+                self.detail_entry_idx = None;
+                self.definition_pending = Some(entry_idx);
+            }
+        }
+    }
+
+    fn show_definition(
+        &mut self,
+        dict: &DictionaryStore,
+        entry_idx: usize,
+        definition: Result<String>,
+    ) {
+        self.definition_pending = None;
+        match definition {
             Ok(definition) => {
                 let entry = &dict.entries[entry_idx];
                 let plain_text = html_to_plain_text(&definition);
@@ -115,19 +430,123 @@ impl SearchState {
                 } else {
                     plain_text
                 };
-                self.detail_text = format!(
+                let detail = format!(
                     "{}\n来源词典: {}\n\n{}",
                     entry.word, entry.source, text_body
                 );
+                self.set_entry_detail(detail, cross_references(&definition));
                 self.detail_entry_idx = Some(entry_idx);
-                self.detail_scroll = 0;
-                self.detail_line_count = count_lines(&self.detail_text);
             }
             Err(err) => {
-                self.detail_text = format!("读取词条失败: {err}");
+                self.set_detail_text(format!("读取词条失败: {err}"));
                 self.detail_entry_idx = None;
-                self.detail_scroll = 0;
-                self.detail_line_count = count_lines(&self.detail_text);
+            }
+        }
+    }
+
+    fn set_detail_text(&mut self, text: String) {
+        self.detail_is_entry = false;
+        self.detail_links.clear();
+        self.replace_detail_text(text);
+    }
+
+    fn set_entry_detail(&mut self, text: String, links: Vec<CrossRef>) {
+        self.detail_is_entry = true;
+        self.detail_links = links;
+        self.replace_detail_text(text);
+    }
+
+    fn replace_detail_text(&mut self, text: String) {
+        self.detail_text = text;
+        self.detail_styled = None;
+        self.detail_scroll = 0;
+        self.on_detail_changed();
+    }
+
+    /// 带样式的详情同时保留一份纯文本，用于 AI 结果切换等场景。
+    fn set_detail_styled(&mut self, text: Text<'static>) {
+        self.definition_pending = None;
+        self.detail_text = text_to_plain(&text);
+        self.detail_styled = Some(text);
+        self.detail_is_entry = false;
+        self.detail_links.clear();
+        self.detail_scroll = 0;
+        self.on_detail_changed();
+    }
+
+    fn detail_display_text(&self) -> Text<'static> {
+        match &self.detail_styled {
+            Some(styled) => styled.clone(),
+            None if self.detail_is_entry => {
+                entry_text(&self.detail_text, &self.detail_links, &self.theme)
+            }
+            None => Text::raw(self.detail_text.clone()),
+        }
+    }
+
+    fn set_detail_width(&mut self, width: u16) {
+        if self.detail_width != width {
+            self.detail_width = width;
+            self.recount_detail_lines();
+        }
+    }
+
+    fn on_detail_changed(&mut self) {
+        self.recount_detail_lines();
+        if let Some(mut find) = self.find.take() {
+            find.update(&self.detail_display_text());
+            self.find = Some(find);
+            self.jump_to_find_match();
+        }
+    }
+
+    fn recount_detail_lines(&mut self) {
+        self.detail_line_count = if self.detail_width == 0 {
+            count_lines(&self.detail_text)
+        } else {
+            Paragraph::new(self.detail_display_text())
+                .wrap(Wrap { trim: false })
+                .line_count(self.detail_width)
+                .max(1)
+        };
+    }
+
+    /// 滚动到当前匹配所在的折行后的行。
+    fn jump_to_find_match(&mut self) {
+        let Some(find) = &self.find else {
+            return;
+        };
+        if let Some(row) = find.current_row(&self.detail_display_text(), self.detail_width) {
+            self.detail_scroll = row.saturating_sub(FIND_CONTEXT_ROWS);
+        }
+    }
+
+    fn edit_find(&mut self, edit: impl FnOnce(&mut DetailFind)) {
+        let text = self.detail_display_text();
+        if let Some(find) = self.find.as_mut() {
+            edit(find);
+            find.update(&text);
+        }
+        self.jump_to_find_match();
+    }
+
+    fn step_find(&mut self, forward: bool) {
+        if let Some(find) = self.find.as_mut() {
+            if forward {
+                find.next();
+            } else {
+                find.prev();
+            }
+        }
+        self.jump_to_find_match();
+    }
+
+    fn apply_ai_update(&mut self, update: AiUpdate) {
+        match update {
+            AiUpdate::Status(status) => self.status_text = status,
+            AiUpdate::Detail { text, status } => {
+                self.set_detail_styled(text);
+                self.status_text = status;
             }
         }
     }
@@ -146,92 +565,615 @@ impl Default for SearchState {
     fn default() -> Self {
         let text = "开始输入关键词后，会在每次输入/删除字符时自动查询。".to_string();
         Self {
-            query: String::new(),
+            query: LineEditor::default(),
             result_indexes: Vec::new(),
             selected: 0,
             detail_text: text.clone(),
+            detail_styled: None,
             detail_entry_idx: None,
+            detail_is_entry: false,
+            detail_links: Vec::new(),
             detail_scroll: 0,
             detail_line_count: count_lines(&text),
+            detail_width: 0,
+            find: None,
             status_text: String::new(),
+            ai_status: None,
+            active_template: 0,
+            template_picker: None,
+            dragging_split: false,
+            search_due: None,
+            search_pending: None,
+            definition_pending: None,
+            entry_action: None,
+            spinner_tick: 0,
+            ai_focus: None,
+            tab_id: 0,
+            theme: Theme::default(),
+            help: None,
         }
     }
 }
 
+/// 搜索页的标签页。当前标签页的状态由 `run_search` 取出单独持有，
+/// `parked` 中对应位置是占位的默认值，切换时两者交换。
+struct SearchTabs {
+    parked: Vec<SearchState>,
+    active: usize,
+    /// 下一个新标签页的编号，第一个标签页为 0
+    next_id: usize,
+}
+
+impl SearchTabs {
+    fn new() -> Self {
+        Self {
+            parked: vec![SearchState::default()],
+            active: 0,
+            next_id: 1,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.parked.len()
+    }
+
+    fn switch_to(&mut self, state: &mut SearchState, index: usize) {
+        mem::swap(state, &mut self.parked[self.active]);
+        self.active = index;
+        mem::swap(state, &mut self.parked[index]);
+    }
+
+    /// 在当前标签页之后插入并分配编号，返回新标签页的位置。
+    fn insert(&mut self, mut tab: SearchState) -> usize {
+        tab.tab_id = self.next_id;
+        self.next_id += 1;
+        self.parked.insert(self.active + 1, tab);
+        self.active + 1
+    }
+
+    /// 关闭当前标签页并切到右侧 (没有时左侧) 的标签页；只剩一个时不关闭，返回 `false`。
+    fn close(&mut self, state: &mut SearchState) -> bool {
+        if self.parked.len() <= 1 {
+            return false;
+        }
+        self.parked.remove(self.active);
+        self.active = self.active.min(self.parked.len() - 1);
+        *state = mem::take(&mut self.parked[self.active]);
+        true
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        for tab in &mut self.parked {
+            tab.theme = theme.clone();
+        }
+    }
+
+    /// 标签栏的标题：序号加查询内容。
+    fn titles(&self, state: &SearchState) -> Vec<String> {
+        (0..self.len())
+            .map(|idx| {
+                let tab = if idx == self.active {
+                    state
+                } else {
+                    &self.parked[idx]
+                };
+                let word = tab.query_word();
+                let title = if word.is_empty() {
+                    "新标签页".to_string()
+                } else if word.chars().count() > TAB_TITLE_CHARS {
+                    let head: String = word.chars().take(TAB_TITLE_CHARS - 1).collect();
+                    format!("{head}…")
+                } else {
+                    word.to_string()
+                };
+                format!("{} {title}", idx + 1)
+            })
+            .collect()
+    }
+}
+
+/// 切换标签页，保存当前标签页显示的 AI 结果槽。
+fn switch_tab(
+    tabs: &mut SearchTabs,
+    state: &mut SearchState,
+    index: usize,
+    ai: &mut AiSession,
+    store: &DictionaryStore,
+    definition_cache: &mut DefinitionCache,
+    worker: &LookupWorker,
+) {
+    state.ai_focus = ai.focus();
+    tabs.switch_to(state, index);
+    enter_tab(state, ai, store, definition_cache, worker);
+}
+
+/// 进入标签页后恢复它的 AI 结果槽和未完成的查询。
+/// 离开期间 AI 查询在后台完成时，正在显示该结果的标签页换上完成后的内容。
+fn enter_tab(
+    state: &mut SearchState,
+    ai: &mut AiSession,
+    store: &DictionaryStore,
+    definition_cache: &mut DefinitionCache,
+    worker: &LookupWorker,
+) {
+    state.resume_lookup(store, definition_cache, worker);
+    if let Some(AiUpdate::Detail { text, status }) = ai.restore_focus(state.ai_focus.clone()) {
+        if state.detail_styled.is_some() && text_to_plain(&text) != state.detail_text {
+            state.apply_ai_update(AiUpdate::Detail { text, status });
+        }
+    }
+}
+
+/// 记录详情栏当前显示的词条及开始显示的时间，停留够久才算一次查看。
+#[derive(Default)]
+struct ViewTracker {
+    current: Option<(usize, Instant)>,
+    logged: bool,
+}
+
+impl ViewTracker {
+    /// 返回刚好达到停留时长、需要记入历史的词条。
+    fn observe(&mut self, entry_idx: Option<usize>, dwell_secs: u64) -> Option<usize> {
+        if self.current.map(|(idx, _)| idx) != entry_idx {
+            self.current = entry_idx.map(|idx| (idx, Instant::now()));
+            self.logged = false;
+        }
+        let (idx, since) = self.current?;
+        if self.logged || dwell_secs == 0 || since.elapsed() < Duration::from_secs(dwell_secs) {
+            return None;
+        }
+        self.logged = true;
+        Some(idx)
+    }
+}
+
 fn count_lines(text: &str) -> usize {
     text.lines().count().max(1)
 }
 
-fn handle_query_backspace(
+fn is_plain_char(code: &KeyCode, modifiers: KeyModifiers) -> bool {
+    matches!(code, KeyCode::Char(_))
+        && !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+}
+
+/// 搜索页的帮助：快捷键来自 `keymap`，其余来自处理按键时使用的按键表。
+fn search_help(keymap: &Keymap) -> HelpOverlay {
+    let mut sections = keymap.help_sections();
+    sections.push(HelpSection::from_keys("输入框", &EDIT_KEYS, &EDIT_NOTES));
+    sections.push(HelpSection::from_keys("输入查找内容", &FIND_EDIT_KEYS, &[]));
+    sections.push(HelpSection::from_keys(
+        "查找结果跳转",
+        &FIND_KEYS,
+        &[("其他字符", "结束查找并继续输入查询")],
+    ));
+    let mouse = MouseAction::ALL
+        .iter()
+        .map(|action| (action.trigger().to_string(), action.label().to_string()))
+        .collect();
+    sections.push(HelpSection::new("鼠标", mouse));
+    HelpOverlay::new("搜索页快捷键", sections)
+}
+
+/// 按配置重建快捷键和主题，返回两者的警告。
+fn load_keymap_and_theme(state: &mut SearchState, config: &Config) -> (Keymap, Vec<String>) {
+    let mut reserved = reserved_keys("输入框", &EDIT_KEYS);
+    reserved.extend(reserved_keys("输入查找内容时", &FIND_EDIT_KEYS));
+    reserved.extend(reserved_keys("查找跳转时", &FIND_KEYS));
+    let (keymap, mut warnings) = Keymap::from_config(&config.keymap, &reserved);
+    let (theme, theme_warnings) = Theme::from_config(config);
+    state.theme = theme;
+    warnings.extend(theme_warnings);
+    (keymap, warnings)
+}
+
+/// 输入框内容改变后作废进行中的搜索，停顿 `SEARCH_DEBOUNCE` 后再重新搜索并选中第一条结果。
+fn on_query_changed(
     state: &mut SearchState,
-    store: &mut DictionaryStore,
-    result_cache: &mut QueryResultCache,
+    store: &DictionaryStore,
     definition_cache: &mut DefinitionCache,
+    worker: &LookupWorker,
 ) {
-    if state.query.pop().is_none() {
-        return;
+    state.selected = 0;
+    worker.cancel_search();
+    state.search_pending = None;
+    if state.query_word().is_empty() {
+        state.search_due = None;
+        state.set_results(Vec::new());
+        state.refresh_detail(store, definition_cache, worker);
+    } else {
+        state.search_due = Some(Instant::now() + SEARCH_DEBOUNCE);
     }
+}
 
-    state.selected = 0;
-    state.update_results(store, result_cache);
-    state.refresh_detail(store, definition_cache);
+/// 处理后台线程的结果，只采用仍对应当前输入和选中项的结果；读到的词条总是放入缓存。
+fn apply_lookup_result(
+    state: &mut SearchState,
+    result: LookupResult,
+    store: &DictionaryStore,
+    definition_cache: &mut DefinitionCache,
+    worker: &LookupWorker,
+) {
+    match result {
+        LookupResult::Search {
+            generation,
+            indexes,
+        } => {
+            if state.search_pending != Some(generation) {
+                return;
+            }
+            state.search_pending = None;
+            state.set_results(indexes);
+            state.refresh_detail(store, definition_cache, worker);
+        }
+        LookupResult::Definition {
+            entry_idx,
+            definition,
+        } => {
+            if let Ok(definition) = &definition {
+                definition_cache.put(store, entry_idx, definition.clone());
+            }
+            state.entry_loaded(entry_idx, &definition);
+            if state.definition_pending == Some(entry_idx) {
+                state.show_definition(store, entry_idx, definition);
+            }
+        }
+    }
 }
 
-fn is_prev_key(ch: char) -> bool {
-    matches!(ch, ',' | '<')
+/// 输入查找内容时的按键，Enter 确定后进入 n/N 跳转。
+fn handle_find_edit_key(state: &mut SearchState, key: &KeyEvent) {
+    match fixed_command(&FIND_EDIT_KEYS, key) {
+        Some(FindCommand::Close) => state.find = None,
+        Some(FindCommand::Confirm) => {
+            if let Some(find) = state.find.as_mut() {
+                find.editing = false;
+            }
+        }
+        Some(FindCommand::DeleteChar) => state.edit_find(|find| {
+            find.pattern.pop();
+        }),
+        Some(_) => {}
+        None => {
+            if let KeyCode::Char(ch) = key.code {
+                if !ch.is_control() {
+                    state.edit_find(|find| find.pattern.push(ch));
+                }
+            }
+        }
+    }
 }
 
-fn is_next_key(ch: char) -> bool {
-    matches!(ch, '.' | '>')
+/// 查找结果的跳转键。返回 `false` 表示按键继续交给搜索页处理；
+/// 输入其他字符时结束查找，字符照常进入输入框。
+fn handle_find_key(state: &mut SearchState, key: &KeyEvent) -> bool {
+    match fixed_command(&FIND_KEYS, key) {
+        Some(FindCommand::Close) => state.find = None,
+        Some(FindCommand::Next) => state.step_find(true),
+        Some(FindCommand::Prev) => state.step_find(false),
+        Some(_) => {}
+        None => {
+            if is_plain_char(&key.code, key.modifiers) || key.code == KeyCode::Backspace {
+                state.find = None;
+            }
+            return false;
+        }
+    }
+    true
 }
 
-pub fn run_search(store: &mut DictionaryStore, mut config: Config) -> Result<()> {
-    let rt = tokio::runtime::Runtime::new()?;
+pub fn run_search(store: &DictionaryStore, mut config: Config) -> Result<()> {
+    let (mut ai, mut warning) = AiSession::new()?;
+    let mut notebook = Notebook::load().unwrap_or_else(|err| {
+        warning = Some(format!("读取生词本失败，本次不会保存生词: {err}"));
+        Notebook::unsaved()
+    });
+    let mut history = LookupHistory::load().unwrap_or_else(|err| {
+        warning = Some(format!("读取查询历史失败: {err}"));
+        LookupHistory::default()
+    });
     with_tui(|terminal| {
         let mut state = SearchState::default();
-        let mut result_cache = QueryResultCache::new(SEARCH_CACHE_CAPACITY);
+        let mut tabs = SearchTabs::new();
+        let worker = LookupWorker::new(store.clone());
         let mut definition_cache = DefinitionCache::new(DEFINITION_CACHE_CAPACITY);
-        let mut ai_task: Option<PendingAiQuery> = None;
+        let mut view_tracker = ViewTracker::default();
+        let (mut keymap, config_warnings) = load_keymap_and_theme(&mut state, &config);
+        ai.set_theme(&state.theme);
+        ai.set_keymap(&keymap);
+        if let Some(warning) = &warning {
+            state.status_text = warning.clone();
+        }
+        if !config_warnings.is_empty() {
+            state.status_text = config_warnings.join("; ");
+        }
 
         loop {
-            poll_ai_task(&mut state, &mut ai_task);
-            terminal.draw(|frame| draw_ui(frame, store, &state))?;
+            while let Some(result) = worker.try_recv() {
+                apply_lookup_result(&mut state, result, store, &mut definition_cache, &worker);
+            }
+            if let Some((action, definitions)) = state.take_ready_entry_action() {
+                match action {
+                    EntryAction::CopyDefinition(idx) => {
+                        state.status_text =
+                            copy_definition(loaded_definition(&definitions, idx), &config);
+                    }
+                    EntryAction::SaveWord(idx) => {
+                        state.status_text = save_to_notebook(
+                            idx,
+                            loaded_definition(&definitions, idx),
+                            store,
+                            &ai,
+                            &mut notebook,
+                            &keymap,
+                        );
+                    }
+                    EntryAction::OpenBrowser(idx) => open_browser_preview(
+                        &mut state,
+                        idx,
+                        loaded_definition(&definitions, idx),
+                        store,
+                        &config,
+                        &mut history,
+                    ),
+                    EntryAction::AiQuery(query) => {
+                        let request = build_ai_request(&query, &definitions, &config, store);
+                        let update = ai.start(&config, request, query.refresh, state.tab_id);
+                        state.apply_ai_update(update);
+                        record_ai_lookup(&mut state, &mut history, store, &query);
+                    }
+                }
+            }
+            if state.search_due.is_some_and(|due| Instant::now() >= due) {
+                state.search_due = None;
+                state.search_pending = Some(worker.search(state.query.text()));
+            }
+            if state.lookup_busy() {
+                state.spinner_tick = state.spinner_tick.wrapping_add(1);
+            }
+            if let Some(update) = ai.poll(&config, state.tab_id) {
+                state.apply_ai_update(update);
+            }
+            state.ai_status = ai.pending_status(state.tab_id);
+            if let Some(idx) =
+                view_tracker.observe(state.detail_entry_idx, config.history_dwell_secs)
+            {
+                let entry = &store.entries[idx];
+                record_lookup(
+                    &mut state,
+                    &mut history,
+                    &entry.word,
+                    &entry.source,
+                    LookupKind::View,
+                );
+            }
+            let size = terminal.size()?;
+            let screen = Rect::new(0, 0, size.width, size.height);
+            let detail_area = search_layout(screen, &config).4;
+            state.set_detail_width(detail_area.width.saturating_sub(2));
+            terminal
+                .draw(|frame| draw_ui(frame, store, &state, &tabs, &config, &keymap, &notebook))?;
 
-            if !event::poll(Duration::from_millis(100))? {
+            let timeout = match state.search_due {
+                Some(due) => due.saturating_duration_since(Instant::now()),
+                None if state.lookup_busy() => BUSY_POLL_INTERVAL,
+                None => Duration::from_millis(100),
+            };
+            if !event::poll(timeout)? {
                 continue;
             }
 
             let event = event::read()?;
+            if let Event::Paste(text) = &event {
+                if state.template_picker.is_some() || state.help.is_some() {
+                    continue;
+                }
+                if state.find.as_ref().is_some_and(|find| find.editing) {
+                    state.edit_find(|find| find.pattern.push_str(text.trim()));
+                } else {
+                    state.find = None;
+                    state.query.insert_str(text);
+                    on_query_changed(&mut state, store, &mut definition_cache, &worker);
+                }
+                continue;
+            }
+            if let Event::Mouse(mouse) = event {
+                if state.template_picker.is_none() && state.help.is_none() {
+                    handle_mouse(
+                        &mut state,
+                        mouse,
+                        screen,
+                        store,
+                        &mut definition_cache,
+                        &worker,
+                        &mut config,
+                    );
+                }
+                continue;
+            }
             if let Event::Key(key) = event {
                 if key.kind != KeyEventKind::Press {
                     continue;
                 }
 
-                match key.code {
-                    KeyCode::Esc => break,
-                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
-                    KeyCode::F(2) => {
-                        match open_in_browser_action(&state, store, &mut definition_cache, &config)
-                        {
-                            Ok(()) => state.status_text = "已打开浏览器预览".to_string(),
-                            Err(err) => state.status_text = format!("打开网页失败: {err}"),
+                if let Some(help) = state.help.as_mut() {
+                    if help.handle_key(&key) {
+                        state.help = None;
+                    }
+                    continue;
+                }
+
+                if let Some(selected) = state.template_picker {
+                    if handle_template_picker_key(key.code, selected, &mut state, &config) {
+                        request_ai_query(&mut state, store, &definition_cache, &worker, false);
+                    }
+                    continue;
+                }
+
+                if state.find.as_ref().is_some_and(|find| find.editing) {
+                    handle_find_edit_key(&mut state, &key);
+                    continue;
+                }
+                if state.find.is_some() && handle_find_key(&mut state, &key) {
+                    continue;
+                }
+
+                // 帮助配置成 `?` 这样的字符键时只在输入框为空时打开帮助，否则照常输入
+                let action = keymap.action(&key).filter(|&action| {
+                    action != Action::Help
+                        || !is_plain_char(&key.code, key.modifiers)
+                        || state.query.text().is_empty()
+                });
+                let Some(action) = action else {
+                    if state.query.handle_key(&key) == Some(true) {
+                        on_query_changed(&mut state, store, &mut definition_cache, &worker);
+                    }
+                    continue;
+                };
+
+                match action {
+                    Action::Help => state.help = Some(search_help(&keymap)),
+                    Action::Quit => break,
+                    Action::OpenBrowser => state.request_selected_entry(
+                        EntryAction::OpenBrowser,
+                        "打开网页失败: 当前没有可打开的词条",
+                        store,
+                        &definition_cache,
+                        &worker,
+                    ),
+                    Action::AiQuery | Action::AiRefresh => request_ai_query(
+                        &mut state,
+                        store,
+                        &definition_cache,
+                        &worker,
+                        action == Action::AiRefresh,
+                    ),
+                    Action::AiCancel => match ai.cancel(state.tab_id) {
+                        Some((query, was_focused)) => {
+                            if was_focused {
+                                state.detail_entry_idx = None;
+                                state.refresh_detail(store, &mut definition_cache, &worker);
+                            }
+                            state.status_text = format!("已取消 AI 查询: {query}");
+                        }
+                        None => state.status_text = "没有进行中的 AI 查询".to_string(),
+                    },
+                    Action::AiNext | Action::AiPrev => {
+                        let delta = if action == Action::AiNext { 1 } else { -1 };
+                        match ai.switch(delta) {
+                            Some(update) => state.apply_ai_update(update),
+                            None => state.status_text = "还没有 AI 结果".to_string(),
+                        }
+                    }
+                    Action::Find => {
+                        let pattern = state
+                            .find
+                            .take()
+                            .map(|find| find.pattern)
+                            .unwrap_or_default();
+                        state.find = Some(DetailFind::new(pattern));
+                        state.edit_find(|_| {});
+                    }
+                    Action::SaveWord => state.request_selected_entry(
+                        EntryAction::SaveWord,
+                        "当前没有可保存的词条",
+                        store,
+                        &definition_cache,
+                        &worker,
+                    ),
+                    Action::CopyDefinition => state.request_selected_entry(
+                        EntryAction::CopyDefinition,
+                        "当前没有可复制的词条",
+                        store,
+                        &definition_cache,
+                        &worker,
+                    ),
+                    Action::CopyWord | Action::CopyAi => {
+                        state.status_text = copy_action(action, &state, store, &ai, &config);
+                    }
+                    Action::Notebook => {
+                        match run_notebook(
+                            terminal,
+                            &mut notebook,
+                            store,
+                            &mut definition_cache,
+                            &config,
+                            keymap.key_hint(Action::SaveWord),
+                        ) {
+                            Ok(Some(word)) => {
+                                state.query.set_text(word);
+                                on_query_changed(&mut state, store, &mut definition_cache, &worker);
+                            }
+                            Ok(None) => {}
+                            Err(err) => state.status_text = format!("生词本错误: {err}"),
                         }
+                        terminal.clear()?;
                     }
-                    KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        match open_in_browser_action(&state, store, &mut definition_cache, &config)
-                        {
-                            Ok(()) => state.status_text = "已打开浏览器预览".to_string(),
-                            Err(err) => state.status_text = format!("打开网页失败: {err}"),
+                    Action::Review => {
+                        if let Err(err) = run_review(
+                            terminal,
+                            &mut notebook,
+                            store,
+                            &mut definition_cache,
+                            &state.theme,
+                            keymap.key_hint(Action::SaveWord),
+                        ) {
+                            state.status_text = format!("复习页错误: {err}");
                         }
+                        terminal.clear()?;
                     }
-                    KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        start_ai_query(&rt, &config, &mut state, &mut ai_task, store);
+                    Action::History => {
+                        match run_history(
+                            terminal,
+                            &mut history,
+                            &notebook,
+                            store,
+                            &mut definition_cache,
+                            &config,
+                            keymap.key_hint(Action::SaveWord),
+                        ) {
+                            Ok(Some(word)) => {
+                                state.query.set_text(word);
+                                on_query_changed(&mut state, store, &mut definition_cache, &worker);
+                            }
+                            Ok(None) => {}
+                            Err(err) => state.status_text = format!("查询历史页错误: {err}"),
+                        }
+                        terminal.clear()?;
+                    }
+                    Action::TemplatePicker => {
+                        let selected = config
+                            .word_template_indexes()
+                            .iter()
+                            .position(|&index| index == state.active_template)
+                            .unwrap_or(0);
+                        state.template_picker = Some(selected);
+                    }
+                    Action::AiHistory => {
+                        match run_ai_history(terminal, ai.cache_mut(), &state.theme) {
+                            Ok(Some(record)) => {
+                                state.apply_ai_update(ai.show_cached(&record, state.tab_id));
+                            }
+                            Ok(None) => {}
+                            Err(err) => {
+                                state.status_text = format!("AI 缓存页错误: {err}");
+                            }
+                        }
+                        terminal.clear()?;
                     }
-                    KeyCode::F(4) => {
+                    Action::Config => {
                         match run_config_editor(terminal, config.clone()) {
                             Ok(new_config) => {
                                 config = new_config;
-                                state.status_text = "配置已更新".to_string();
+                                let warnings;
+                                (keymap, warnings) = load_keymap_and_theme(&mut state, &config);
+                                tabs.set_theme(&state.theme);
+                                ai.set_theme(&state.theme);
+                                ai.set_keymap(&keymap);
+                                state.status_text = if warnings.is_empty() {
+                                    "配置已更新".to_string()
+                                } else {
+                                    format!("配置已更新 | {}", warnings.join("; "))
+                                };
                             }
                             Err(err) => {
                                 state.status_text = format!("配置页错误: {err}");
@@ -239,233 +1181,501 @@ pub fn run_search(store: &mut DictionaryStore, mut config: Config) -> Result<()>
                         }
                         terminal.clear()?;
                     }
-                    KeyCode::Char(ch) if is_prev_key(ch) => {
+                    Action::PrevEntry => {
                         state.selected = state.selected.saturating_sub(1);
-                        state.refresh_detail(store, &mut definition_cache);
+                        state.refresh_detail(store, &mut definition_cache, &worker);
+                    }
+                    Action::NextEntry => {
+                        if state.selected + 1 < state.result_indexes.len() {
+                            state.selected += 1;
+                            state.refresh_detail(store, &mut definition_cache, &worker);
+                        }
+                    }
+                    Action::ToggleZoom => {
+                        config.detail_zoom = !config.detail_zoom;
+                        let message = if config.detail_zoom {
+                            format!(
+                                "只看详情，{} 恢复结果列表",
+                                keymap.keys_text(Action::ToggleZoom)
+                            )
+                        } else {
+                            "已恢复结果列表".to_string()
+                        };
+                        save_layout(&mut state, &config, message);
                     }
-                    KeyCode::Char(ch)
-                        if is_next_key(ch) && state.selected + 1 < state.result_indexes.len() =>
-                    {
-                        state.selected += 1;
-                        state.refresh_detail(store, &mut definition_cache);
+                    Action::CycleLayout => {
+                        config.layout_mode = config.layout_mode.next();
+                        config.detail_zoom = false;
+                        let message = format!("布局: {}", config.layout_mode.label());
+                        save_layout(&mut state, &config, message);
+                    }
+                    Action::NewTab => {
+                        if tabs.len() >= MAX_TABS {
+                            state.status_text = format!("最多打开 {MAX_TABS} 个标签页");
+                        } else {
+                            let index = tabs.insert(SearchState {
+                                theme: state.theme.clone(),
+                                ..SearchState::default()
+                            });
+                            switch_tab(
+                                &mut tabs,
+                                &mut state,
+                                index,
+                                &mut ai,
+                                store,
+                                &mut definition_cache,
+                                &worker,
+                            );
+                        }
                     }
-                    KeyCode::Backspace => {
-                        handle_query_backspace(
+                    Action::CloseTab => {
+                        let closed = state.tab_id;
+                        if tabs.close(&mut state) {
+                            ai.close_tab(closed);
+                            enter_tab(&mut state, &mut ai, store, &mut definition_cache, &worker);
+                        } else {
+                            state.status_text = "已是最后一个标签页".to_string();
+                        }
+                    }
+                    Action::NextTab | Action::PrevTab => {
+                        let len = tabs.len();
+                        let index = if action == Action::NextTab {
+                            (tabs.active + 1) % len
+                        } else {
+                            (tabs.active + len - 1) % len
+                        };
+                        switch_tab(
+                            &mut tabs,
                             &mut state,
+                            index,
+                            &mut ai,
                             store,
-                            &mut result_cache,
                             &mut definition_cache,
+                            &worker,
                         );
                     }
-                    KeyCode::Char(ch) if !ch.is_control() => {
-                        state.query.push(ch);
-                        state.selected = 0;
-                        state.update_results(store, &mut result_cache);
-                        state.refresh_detail(store, &mut definition_cache);
+                    Action::ShrinkList | Action::GrowList => {
+                        resize_list(&mut state, &mut config, action == Action::GrowList);
                     }
-                    KeyCode::Up => state.scroll_up(),
-                    KeyCode::Down => state.scroll_down(),
-                    KeyCode::Home => {
+                    Action::ScrollUp => state.scroll_up(),
+                    Action::ScrollDown => state.scroll_down(),
+                    Action::FirstEntry => {
                         state.selected = 0;
-                        state.refresh_detail(store, &mut definition_cache);
+                        state.refresh_detail(store, &mut definition_cache, &worker);
                     }
-                    KeyCode::End if !state.result_indexes.is_empty() => {
-                        state.selected = state.result_indexes.len() - 1;
-                        state.refresh_detail(store, &mut definition_cache);
+                    Action::LastEntry => {
+                        if !state.result_indexes.is_empty() {
+                            state.selected = state.result_indexes.len() - 1;
+                            state.refresh_detail(store, &mut definition_cache, &worker);
+                        }
                     }
-                    KeyCode::PageUp => {
+                    Action::PageUp => {
                         state.selected = state.selected.saturating_sub(PAGE_STEP);
-                        state.refresh_detail(store, &mut definition_cache);
+                        state.refresh_detail(store, &mut definition_cache, &worker);
                     }
-                    KeyCode::PageDown if !state.result_indexes.is_empty() => {
-                        state.selected =
-                            (state.selected + PAGE_STEP).min(state.result_indexes.len() - 1);
-                        state.refresh_detail(store, &mut definition_cache);
+                    Action::PageDown => {
+                        if !state.result_indexes.is_empty() {
+                            state.selected =
+                                (state.selected + PAGE_STEP).min(state.result_indexes.len() - 1);
+                            state.refresh_detail(store, &mut definition_cache, &worker);
+                        }
                     }
-                    _ => {}
                 }
             }
         }
 
-        abort_ai_task(&mut ai_task);
+        ai.abort_all();
         Ok(())
     })
 }
 
-fn start_ai_query(
-    rt: &tokio::runtime::Runtime,
-    config: &Config,
+fn open_browser_preview(
     state: &mut SearchState,
-    ai_task: &mut Option<PendingAiQuery>,
+    entry_idx: usize,
+    definition: Result<&str, &str>,
     store: &DictionaryStore,
+    config: &Config,
+    history: &mut LookupHistory,
 ) {
-    let query = state.query.trim().to_string();
-    if query.is_empty() {
-        state.status_text = "请先输入要查询的内容".to_string();
-        return;
+    let opened = match definition {
+        Ok(raw) => {
+            open_in_browser_action(entry_idx, raw, store, config).map_err(|err| err.to_string())
+        }
+        Err(err) => Err(format!("读取词条失败: {err}")),
+    };
+    match opened {
+        Ok(()) => {
+            state.status_text = "已打开浏览器预览".to_string();
+            let entry = &store.entries[entry_idx];
+            record_lookup(
+                state,
+                history,
+                &entry.word,
+                &entry.source,
+                LookupKind::Browser,
+            );
+        }
+        Err(err) => state.status_text = format!("打开网页失败: {err}"),
     }
+}
 
-    let ai_context = state
-        .selected_entry_index()
-        .map(|idx| {
-            let entry = &store.entries[idx];
-            format!("[词典词条] {} ({})\n", entry.word, entry.source)
-        })
+/// AI 查询的是输入框内容；与引用的词条同名时记下来源词典。
+fn record_ai_lookup(
+    state: &mut SearchState,
+    history: &mut LookupHistory,
+    store: &DictionaryStore,
+    query: &PendingAiQuery,
+) {
+    let source = query
+        .entry_idx
+        .map(|idx| &store.entries[idx])
+        .filter(|entry| entry.word.eq_ignore_ascii_case(&query.query))
+        .map(|entry| entry.source.clone())
         .unwrap_or_default();
+    record_lookup(state, history, &query.query, &source, LookupKind::Ai);
+}
 
-    abort_ai_task(ai_task);
+/// 每次记录后立即保存；保存失败只在状态栏提示，不影响查词。
+fn record_lookup(
+    state: &mut SearchState,
+    history: &mut LookupHistory,
+    word: &str,
+    source: &str,
+    kind: LookupKind,
+) {
+    history.record(word, source, kind);
+    if let Err(err) = history.save() {
+        state.status_text = format!("保存查询历史失败: {err}");
+    }
+}
 
-    state.status_text = format!("AI 查询 '{}' ...", query);
-    state.detail_text = build_ai_pending_detail(&query, &ai_context, 0);
-    state.detail_scroll = 0;
-    state.detail_line_count = count_lines(&state.detail_text);
+/// 取出等待中的操作读到的词条原文。
+fn loaded_definition(definitions: &LoadedDefinitions, entry_idx: usize) -> Result<&str, &str> {
+    match definitions.get(&entry_idx) {
+        Some(Ok(definition)) => Ok(definition),
+        Some(Err(err)) => Err(err),
+        None => Err("词条未读取"),
+    }
+}
 
-    *ai_task = Some(spawn_ai_query(rt, config.clone(), query, ai_context));
+/// 复制当前词条的词头或当前 AI 回答，返回状态文字。
+fn copy_action(
+    action: Action,
+    state: &SearchState,
+    store: &DictionaryStore,
+    ai: &AiSession,
+    config: &Config,
+) -> String {
+    let (what, text) = match action {
+        Action::CopyWord => match state.selected_entry_index() {
+            Some(idx) => ("词头", store.entries[idx].word.clone()),
+            None => ("输入内容", state.query_word().to_string()),
+        },
+        _ => match ai.focused_answer() {
+            Some(answer) => ("AI 回答", answer),
+            None => return "还没有 AI 结果".to_string(),
+        },
+    };
+    copy_text(what, &text, config)
 }
 
-fn abort_ai_task(ai_task: &mut Option<PendingAiQuery>) {
-    if let Some(task) = ai_task.take() {
-        task.handle.abort();
+/// 复制词条的纯文本释义。
+fn copy_definition(definition: Result<&str, &str>, config: &Config) -> String {
+    match definition {
+        Ok(raw) => copy_text("释义", &html_to_plain_text(raw), config),
+        Err(err) => format!("读取词条失败: {err}"),
     }
 }
 
-fn spawn_ai_query(
-    rt: &tokio::runtime::Runtime,
-    config: Config,
-    query: String,
-    context: String,
-) -> PendingAiQuery {
-    let (sender, receiver) = mpsc::channel();
-    let task_query = query.clone();
-    let task_context = context.clone();
-    let handle = rt.spawn(async move {
-        let ai = AiClient::new(config);
-        let result = ai
-            .query_with_context(&task_query, &task_context)
-            .await
-            .map_err(|err| err.to_string());
-        let _ = sender.send(result);
-    });
-
-    PendingAiQuery {
-        query,
-        context,
-        receiver,
-        handle,
-        tick: 0,
+fn copy_text(what: &str, text: &str, config: &Config) -> String {
+    if text.is_empty() {
+        return format!("{what}为空，没有复制");
+    }
+    match copy_to_clipboard(text, &config.clipboard_command) {
+        Ok(method) => format!("已复制{what} ({} 字，{method})", text.chars().count()),
+        Err(err) => format!("复制失败: {err:#}"),
     }
 }
 
-fn poll_ai_task(state: &mut SearchState, ai_task: &mut Option<PendingAiQuery>) {
-    let outcome = {
-        let Some(task) = ai_task.as_mut() else {
-            return;
-        };
+/// 保存读到的词条；该词有缓存的 AI 回答时一并保存。
+fn save_to_notebook(
+    entry_idx: usize,
+    definition: Result<&str, &str>,
+    store: &DictionaryStore,
+    ai: &AiSession,
+    notebook: &mut Notebook,
+    keymap: &Keymap,
+) -> String {
+    let definition = match definition {
+        Ok(raw) => html_to_plain_text(raw),
+        Err(err) => return format!("读取词条失败: {err}"),
+    };
+    let entry = &store.entries[entry_idx];
+    let ai_answer = ai
+        .cache()
+        .latest_for_query(&entry.word)
+        .map(|record| record.answer.clone());
+    let word = entry.word.clone();
+    let added = notebook.upsert(NotebookEntry::new(
+        entry.word.clone(),
+        entry.source.clone(),
+        definition,
+        ai_answer,
+    ));
 
-        match task.receiver.try_recv() {
-            Ok(result) => AiPollOutcome::Complete {
-                query: task.query.clone(),
-                context: task.context.clone(),
-                result,
-            },
-            Err(TryRecvError::Empty) => {
-                task.tick = task.tick.wrapping_add(1);
-                let spinner = AI_SPINNER[(task.tick / 2) % AI_SPINNER.len()];
-                AiPollOutcome::Pending(format!("AI 查询中 {} {}", spinner, task.query))
-            }
-            Err(TryRecvError::Disconnected) => AiPollOutcome::Disconnected(task.query.clone()),
+    match notebook.save() {
+        Ok(()) if added => format!(
+            "已加入生词本: {word} ({} 查看)",
+            keymap.key_hint(Action::Notebook)
+        ),
+        Ok(()) => format!("已更新生词本中的 {word}"),
+        Err(err) => format!("保存生词本失败: {err}"),
+    }
+}
+
+/// 返回 `true` 表示选定了模板，应开始查询。
+fn handle_template_picker_key(
+    code: KeyCode,
+    selected: usize,
+    state: &mut SearchState,
+    config: &Config,
+) -> bool {
+    let templates = config.word_template_indexes();
+    let count = templates.len().max(1);
+    match code {
+        KeyCode::Esc => state.template_picker = None,
+        KeyCode::Up => state.template_picker = Some(selected.saturating_sub(1)),
+        KeyCode::Down => state.template_picker = Some((selected + 1).min(count - 1)),
+        KeyCode::Enter => {
+            state.template_picker = None;
+            state.active_template = templates.get(selected).copied().unwrap_or(0);
+            return true;
         }
+        _ => {}
+    }
+    false
+}
+
+/// 记下当前输入的 AI 查询：单词引用选中的词条，句子回查其中的难词，读到这些词条后再发送。
+fn request_ai_query(
+    state: &mut SearchState,
+    store: &DictionaryStore,
+    definition_cache: &DefinitionCache,
+    worker: &LookupWorker,
+    refresh: bool,
+) {
+    let query = state.query_word().to_string();
+    if query.is_empty() {
+        state.status_text = "请先输入要查询的内容".to_string();
+        return;
+    }
+
+    let (entry_idx, glossary) = if is_sentence(&query) {
+        (None, glossary_entries(&query, store))
+    } else {
+        (state.selected_entry_index(), Vec::new())
     };
+    let query = PendingAiQuery {
+        query,
+        template: state.active_template,
+        refresh,
+        entry_idx,
+        glossary,
+    };
+    state.request_entry_action(EntryAction::AiQuery(query), store, definition_cache, worker);
+}
 
-    match outcome {
-        AiPollOutcome::Pending(status) => {
-            state.status_text = status;
-        }
-        AiPollOutcome::Complete {
-            query,
-            context,
-            result,
-        } => {
-            match result {
-                Ok(answer) => {
-                    state.detail_text = build_ai_answer_detail(&query, &context, &answer);
-                    state.status_text = format!("AI 查询完成: {query}");
-                }
-                Err(err) => {
-                    state.detail_text = format!("--- AI 查询失败: {query} ---\n\n{err}");
-                    state.status_text = format!("AI 查询失败: {query}");
-                }
-            }
-            state.detail_scroll = 0;
-            state.detail_line_count = count_lines(&state.detail_text);
-            *ai_task = None;
+/// 句中难词及其在词典中的词条，最多 `MAX_GLOSSARY_WORDS` 个。
+fn glossary_entries(query: &str, store: &DictionaryStore) -> Vec<(String, usize)> {
+    let mut entries = Vec::new();
+    for word in difficult_words(query) {
+        if entries.len() >= MAX_GLOSSARY_WORDS {
+            break;
         }
-        AiPollOutcome::Disconnected(query) => {
-            state.detail_text = format!("--- AI 查询失败: {query} ---\n\nAI 任务已中断。");
-            state.detail_scroll = 0;
-            state.detail_line_count = count_lines(&state.detail_text);
-            state.status_text = format!("AI 查询中断: {query}");
-            *ai_task = None;
+        if let Some(idx) = word_forms(&word)
+            .iter()
+            .find_map(|form| store.find_exact(form, None))
+        {
+            entries.push((word, idx));
         }
     }
+    entries
 }
 
-fn build_ai_pending_detail(query: &str, context: &str, tick: usize) -> String {
-    let spinner = AI_SPINNER[(tick / 2) % AI_SPINNER.len()];
-    let header = if context.trim().is_empty() {
-        String::new()
-    } else {
-        format!("{}\n", context.trim())
+fn build_ai_request(
+    query: &PendingAiQuery,
+    definitions: &LoadedDefinitions,
+    config: &Config,
+    store: &DictionaryStore,
+) -> AiRequest {
+    if is_sentence(&query.query) {
+        return build_translation_request(query, definitions, config, store);
+    }
+
+    let template = config.prompt_template(query.template);
+    let (context, definition, source) = match query.entry_idx {
+        Some(idx) => {
+            let definition = loaded_definition(definitions, idx)
+                .map(html_to_plain_text)
+                .unwrap_or_default();
+            let entry = &store.entries[idx];
+            (
+                format!("[词典词条] {} ({})\n", entry.word, entry.source),
+                definition
+                    .chars()
+                    .take(MAX_PROMPT_DEFINITION_CHARS)
+                    .collect::<String>(),
+                entry.source.clone(),
+            )
+        }
+        None => Default::default(),
     };
 
-    format!(
-        "{}--- AI 回答: {} ---\n\nAI 查询中 {} \n\n可以继续输入、滚动或打开配置页；结果返回后会自动更新到这里。",
-        header, query, spinner
-    )
+    let user_prompt = render_template(
+        &template.template,
+        &PromptVars {
+            word: &query.query,
+            language: &config.answer_language,
+            context: context.trim(),
+            definition: &definition,
+            source: &source,
+        },
+    );
+
+    AiRequest {
+        query: query.query.clone(),
+        context,
+        template: template.name,
+        user_prompt,
+    }
 }
 
-fn build_ai_answer_detail(query: &str, context: &str, answer: &str) -> String {
-    if context.trim().is_empty() {
-        format!("--- AI 回答: {} ---\n\n{}", query, answer)
+/// 句子翻译：句中难词的释义写进提示词，词条对应关系写进上下文行。
+fn build_translation_request(
+    query: &PendingAiQuery,
+    definitions: &LoadedDefinitions,
+    config: &Config,
+    store: &DictionaryStore,
+) -> AiRequest {
+    let template = config.translation_template();
+    let mut glossary = Vec::new();
+    let mut links = Vec::new();
+    for (word, idx) in &query.glossary {
+        let snippet = loaded_definition(definitions, *idx)
+            .map(html_to_plain_text)
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(GLOSSARY_SNIPPET_CHARS)
+            .collect::<String>();
+        let entry = &store.entries[*idx];
+        glossary.push(format!("- {} ({}): {}", entry.word, entry.source, snippet));
+        links.push(format!("{word} → {} ({})", entry.word, entry.source));
+    }
+
+    let context = if links.is_empty() {
+        "[句子翻译] 词典中没有找到难词\n".to_string()
     } else {
-        format!(
-            "{}\n--- AI 回答: {} ---\n\n{}",
-            context.trim(),
-            query,
-            answer
-        )
+        format!("[句子翻译] 词典难词: {}\n", links.join("; "))
+    };
+    let definition = if glossary.is_empty() {
+        "(无)".to_string()
+    } else {
+        glossary.join("\n")
+    };
+    let user_prompt = render_template(
+        &template.template,
+        &PromptVars {
+            word: &query.query,
+            language: &config.answer_language,
+            context: context.trim(),
+            definition: &definition,
+            source: "",
+        },
+    );
+
+    AiRequest {
+        query: query.query.clone(),
+        context,
+        template: template.name,
+        user_prompt,
     }
 }
 
-fn draw_ui(frame: &mut Frame, store: &DictionaryStore, state: &SearchState) {
-    let rows = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Length(1),
-            Constraint::Min(1),
-        ])
-        .split(frame.area());
+fn draw_ui(
+    frame: &mut Frame,
+    store: &DictionaryStore,
+    state: &SearchState,
+    tabs: &SearchTabs,
+    config: &Config,
+    keymap: &Keymap,
+    notebook: &Notebook,
+) {
+    let (tab_area, input_area, tip_area, list_area, detail_area) =
+        search_layout(frame.area(), config);
 
-    let input = Paragraph::new(state.query.as_str()).block(
-        Block::default()
-            .title("输入(动态查词)")
-            .borders(Borders::ALL),
-    );
-    frame.render_widget(input, rows[0]);
+    let tab_bar = Tabs::new(tabs.titles(state))
+        .select(tabs.active)
+        .highlight_style(state.theme.highlight)
+        .divider("|");
+    frame.render_widget(tab_bar, tab_area);
 
-    let tip = Paragraph::new(format!(
-        "输入/退格实时查询 | ,/. 切换词条 | ↑/↓ 滚动详情 | Ctrl+G AI查询 | F4 配置 | Ctrl+O/F2 网页 | Esc 退出 | 命中 {} 条",
-        state.result_indexes.len(),
-    ));
-    frame.render_widget(tip, rows[1]);
+    let input_width = input_area.width.saturating_sub(2);
+    let find_editing = state.find.as_ref().filter(|find| find.editing);
+    let (visible, cursor_x) = match find_editing {
+        Some(find) => single_line_view(&find.pattern, find.pattern.len(), input_width),
+        None => state.query.view(input_width),
+    };
+    let input_block = match find_editing {
+        Some(_) => Block::default()
+            .title("在详情中查找 (Enter 确定后 n/N 跳转，Esc 取消)")
+            .borders(Borders::ALL)
+            .border_style(state.theme.border_editing),
+        None => {
+            let input_title = if is_sentence(state.query_word()) {
+                format!(
+                    "输入(句子翻译模式，{} 翻译并讲解语法)",
+                    keymap.keys_text(Action::AiQuery)
+                )
+            } else {
+                "输入(动态查词)".to_string()
+            };
+            let input_title = match source_filter_title(state, store) {
+                Some(filter) => format!("{input_title} {filter}"),
+                None => input_title,
+            };
+            Block::default()
+                .title(input_title)
+                .borders(Borders::ALL)
+                .border_style(state.theme.border)
+        }
+    };
+    let input = Paragraph::new(visible).block(input_block);
+    frame.render_widget(input, input_area);
+    if state.template_picker.is_none() && state.help.is_none() {
+        frame.set_cursor_position(Position::new(input_area.x + 1 + cursor_x, input_area.y + 1));
+    }
 
-    let columns = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
-        .split(rows[2]);
+    let due_count = notebook.due_count(end_of_today());
+    let keys = keymap
+        .tip_line(|action| (action == Action::Review).then(|| format!("今日待复习 {due_count}")));
+    let spinner = LOOKUP_SPINNER[state.spinner_tick % LOOKUP_SPINNER.len()];
+    let progress = if state.searching() {
+        format!("{spinner} 搜索中")
+    } else if state.loading_definition() {
+        format!(
+            "命中 {} 条 | {spinner} 读取词条",
+            state.result_indexes.len()
+        )
+    } else {
+        format!("命中 {} 条", state.result_indexes.len())
+    };
+    let tip = Paragraph::new(format!("输入/退格实时查询 | {keys} | {progress}"));
+    frame.render_widget(tip, tip_area);
 
-    let list_items: Vec<ListItem> = if state.query.trim().is_empty() {
+    let list_items: Vec<ListItem> = if state.query_word().is_empty() {
         vec![ListItem::new("请输入关键词...")]
     } else if state.result_indexes.is_empty() {
         vec![ListItem::new("没有匹配结果")]
@@ -481,33 +1691,315 @@ fn draw_ui(frame: &mut Frame, store: &DictionaryStore, state: &SearchState) {
     };
 
     let list = List::new(list_items)
-        .block(Block::default().title("搜索结果").borders(Borders::ALL))
-        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .block(
+            Block::default()
+                .title("搜索结果")
+                .borders(Borders::ALL)
+                .border_style(state.theme.border),
+        )
+        .highlight_style(state.theme.highlight)
         .highlight_symbol(">> ");
 
     let mut list_state = ListState::default();
     if !state.result_indexes.is_empty() {
         list_state.select(Some(state.selected));
     }
-    frame.render_stateful_widget(list, columns[0], &mut list_state);
+    frame.render_stateful_widget(list, list_area, &mut list_state);
+
+    let detail_title = build_detail_title(state, detail_area.width);
+    frame.render_widget(detail_paragraph(state, detail_title), detail_area);
+
+    if let Some(selected) = state.template_picker {
+        draw_template_picker(frame, config, &state.theme, selected);
+    }
+    if let Some(help) = &state.help {
+        help.draw(frame, &state.theme);
+    }
+}
 
+/// 详情栏的段落，绘制和鼠标点击定位共用。
+fn detail_paragraph(state: &SearchState, title: Line<'static>) -> Paragraph<'static> {
     let scroll = state.detail_scroll.min(u16::MAX as usize) as u16;
-    let detail_title = build_detail_title(state, columns[1].width);
-    let detail = Paragraph::new(state.detail_text.as_str())
-        .block(Block::default().title(detail_title).borders(Borders::ALL))
+    let detail_text = match &state.find {
+        Some(find) => find.highlight(state.detail_display_text(), &state.theme),
+        None => state.detail_display_text(),
+    };
+    Paragraph::new(detail_text)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(state.theme.border),
+        )
         .scroll((scroll, 0))
-        .wrap(Wrap { trim: false });
-    frame.render_widget(detail, columns[1]);
+        .wrap(Wrap { trim: false })
+}
+
+/// 输入框标题中显示的词典过滤，没有 `@名称` 前缀时为 `None`。
+fn source_filter_title(state: &SearchState, store: &DictionaryStore) -> Option<String> {
+    let filter = DictionaryStore::split_source_filter(state.query.text()).0?;
+    let sources = store.matching_sources(filter);
+    let title = match sources.as_slice() {
+        _ if filter.is_empty() => format!("[@名称 只查某部词典，共 {} 部]", store.sources.len()),
+        [] => format!("[没有名称包含 \"{filter}\" 的词典]"),
+        [only] => format!("[只查 {}]", store.sources[*only].name),
+        [first, ..] => format!(
+            "[只查 {} 等 {} 部词典]",
+            store.sources[*first].name,
+            sources.len()
+        ),
+    };
+    Some(title)
+}
+
+/// 结果列表与详情栏的实际排布。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Arrangement {
+    Columns,
+    Rows,
+    DetailOnly,
+}
+
+/// 由布局方式、窗口宽度和是否只看详情决定排布。
+fn arrangement(config: &Config, width: u16) -> Arrangement {
+    if config.detail_zoom {
+        return Arrangement::DetailOnly;
+    }
+    match config.layout_mode {
+        LayoutMode::Horizontal => Arrangement::Columns,
+        LayoutMode::Vertical => Arrangement::Rows,
+        LayoutMode::Auto if width < config.stack_below_width => Arrangement::Rows,
+        LayoutMode::Auto => Arrangement::Columns,
+    }
+}
+
+/// 标签栏、输入框、提示行、结果列表和详情栏的位置；只看详情时结果列表为空区域。
+fn search_layout(area: Rect, config: &Config) -> (Rect, Rect, Rect, Rect, Rect) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ])
+        .split(area);
+    let list_percent = config
+        .list_percent
+        .clamp(MIN_LIST_PERCENT, MAX_LIST_PERCENT);
+    let direction = match arrangement(config, area.width) {
+        Arrangement::Columns => Direction::Horizontal,
+        Arrangement::Rows => Direction::Vertical,
+        Arrangement::DetailOnly => {
+            return (rows[0], rows[1], rows[2], Rect::default(), rows[3]);
+        }
+    };
+    let panes = Layout::default()
+        .direction(direction)
+        .constraints([
+            Constraint::Percentage(list_percent),
+            Constraint::Percentage(100 - list_percent),
+        ])
+        .split(rows[3]);
+    (rows[0], rows[1], rows[2], panes[0], panes[1])
 }
 
-fn build_detail_title(state: &SearchState, area_width: u16) -> String {
-    let title = if state.status_text.is_empty() {
-        "词条详情".to_string()
+/// 布局改动写回配置文件，下次启动沿用。
+fn save_layout(state: &mut SearchState, config: &Config, message: String) {
+    state.status_text = match config.save() {
+        Ok(()) => message,
+        Err(err) => format!("{message}，但保存配置失败: {err}"),
+    };
+}
+
+fn resize_list(state: &mut SearchState, config: &mut Config, grow: bool) {
+    let percent = if grow {
+        config.list_percent.saturating_add(LIST_PERCENT_STEP)
+    } else {
+        config.list_percent.saturating_sub(LIST_PERCENT_STEP)
+    };
+    config.list_percent = percent.clamp(MIN_LIST_PERCENT, MAX_LIST_PERCENT);
+    config.detail_zoom = false;
+    save_layout(
+        state,
+        config,
+        format!("结果列表占 {}%", config.list_percent),
+    );
+}
+
+/// 点击结果行选中词条，滚轮滚动指针下的栏，拖动两栏之间的边框调整比例，
+/// 点击详情中的交叉引用跳转查询。
+fn handle_mouse(
+    state: &mut SearchState,
+    mouse: MouseEvent,
+    screen: Rect,
+    store: &DictionaryStore,
+    definition_cache: &mut DefinitionCache,
+    worker: &LookupWorker,
+    config: &mut Config,
+) {
+    let (_, _, _, list_area, detail_area) = search_layout(screen, config);
+    let position = Position::new(mouse.column, mouse.row);
+    let stacked = arrangement(config, screen.width) == Arrangement::Rows;
+    let on_split = if stacked {
+        (list_area.x..list_area.right()).contains(&mouse.column)
+            && (list_area.bottom().saturating_sub(1)..=detail_area.y).contains(&mouse.row)
     } else {
-        format!("词条详情 | {}", state.status_text)
+        (list_area.y..list_area.bottom()).contains(&mouse.row)
+            && (list_area.right().saturating_sub(1)..=detail_area.x).contains(&mouse.column)
+    };
+
+    let action = match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) if on_split => MouseAction::ResizeSplit,
+        MouseEventKind::Drag(MouseButton::Left) | MouseEventKind::Up(MouseButton::Left)
+            if state.dragging_split =>
+        {
+            MouseAction::ResizeSplit
+        }
+        MouseEventKind::Down(MouseButton::Left) if list_area.contains(position) => {
+            MouseAction::SelectRow
+        }
+        MouseEventKind::Down(MouseButton::Left) if detail_area.contains(position) => {
+            MouseAction::FollowLink
+        }
+        MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => MouseAction::Scroll,
+        _ => return,
     };
+
+    match action {
+        MouseAction::ResizeSplit => match mouse.kind {
+            MouseEventKind::Down(_) => state.dragging_split = true,
+            MouseEventKind::Drag(_) => {
+                let (offset, total) = if stacked {
+                    (
+                        mouse.row.saturating_sub(list_area.y),
+                        list_area.height + detail_area.height,
+                    )
+                } else {
+                    (
+                        mouse.column.saturating_sub(list_area.x),
+                        list_area.width + detail_area.width,
+                    )
+                };
+                let percent = (u32::from(offset) + 1) * 100 / u32::from(total).max(1);
+                config.list_percent = (percent as u16).clamp(MIN_LIST_PERCENT, MAX_LIST_PERCENT);
+            }
+            _ => {
+                state.dragging_split = false;
+                save_layout(
+                    state,
+                    config,
+                    format!("结果列表占 {}%", config.list_percent),
+                );
+            }
+        },
+        MouseAction::SelectRow => {
+            let inner = list_area.inner(Margin::new(1, 1));
+            if state.result_indexes.is_empty() || !inner.contains(position) {
+                return;
+            }
+            // 与 ListState 相同的滚动规则：选中行超出可见范围时刚好停在最后一行
+            let offset = (state.selected + 1).saturating_sub(inner.height as usize);
+            let clicked = offset + (mouse.row - inner.y) as usize;
+            if clicked < state.result_indexes.len() {
+                state.selected = clicked;
+                state.refresh_detail(store, definition_cache, worker);
+            }
+        }
+        MouseAction::FollowLink => {
+            let paragraph = detail_paragraph(state, Line::default());
+            let Some(link) = link_at(paragraph, detail_area, position, &state.detail_links) else {
+                return;
+            };
+            let target = link.target.clone();
+            state.find = None;
+            state.status_text = format!("跳转到 {target}");
+            state.query.set_text(target);
+            on_query_changed(state, store, definition_cache, worker);
+        }
+        MouseAction::Scroll => {
+            let down = mouse.kind == MouseEventKind::ScrollDown;
+            if detail_area.contains(position) {
+                if down {
+                    state.scroll_down();
+                } else {
+                    state.scroll_up();
+                }
+            } else if list_area.contains(position) && !state.result_indexes.is_empty() {
+                state.selected = if down {
+                    (state.selected + 1).min(state.result_indexes.len() - 1)
+                } else {
+                    state.selected.saturating_sub(1)
+                };
+                state.refresh_detail(store, definition_cache, worker);
+            }
+        }
+    }
+}
+
+fn draw_template_picker(frame: &mut Frame, config: &Config, theme: &Theme, selected: usize) {
+    let items: Vec<ListItem> = config
+        .word_template_indexes()
+        .into_iter()
+        .map(|index| ListItem::new(config.prompt_templates[index].name.as_str()))
+        .collect();
+    let height = (items.len() as u16).saturating_add(2).max(3);
+    let area = centered_rect(frame.area(), 40, height);
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title("提示词模板 (Enter 查询，Esc 取消)")
+                .borders(Borders::ALL)
+                .border_style(theme.border_focus),
+        )
+        .highlight_style(theme.highlight)
+        .highlight_symbol(">> ");
+    let mut list_state = ListState::default();
+    list_state.select(Some(selected));
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
+
+fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// 详情栏标题，状态文字按主题的 status/error 样式显示。
+fn build_detail_title(state: &SearchState, area_width: u16) -> Line<'static> {
+    let mut title = "词条详情".to_string();
+    if let Some(find) = &state.find {
+        title.push_str(&format!(" | {}", find.status()));
+    }
+    let prefix_chars = title.chars().count();
+    if !state.status_text.is_empty() {
+        title.push_str(&format!(" | {}", state.status_text));
+    }
+    if let Some(ai_status) = &state.ai_status {
+        title.push_str(&format!(" | {ai_status}"));
+    }
     let max_chars = area_width.saturating_sub(2) as usize;
-    truncate_with_ellipsis(&title, max_chars)
+    let truncated = truncate_with_ellipsis(&title, max_chars);
+    let split = truncated
+        .char_indices()
+        .nth(prefix_chars)
+        .map_or(truncated.len(), |(idx, _)| idx);
+    let (prefix, status) = truncated.split_at(split);
+    Line::from(vec![
+        Span::raw(prefix.to_string()),
+        Span::styled(
+            status.to_string(),
+            state.theme.status_style(&state.status_text),
+        ),
+    ])
 }
 
 fn truncate_with_ellipsis(text: &str, max_chars: usize) -> String {
@@ -527,19 +2019,14 @@ fn truncate_with_ellipsis(text: &str, max_chars: usize) -> String {
 }
 
 fn open_in_browser_action(
-    state: &SearchState,
-    dict: &mut DictionaryStore,
-    definition_cache: &mut DefinitionCache,
+    entry_idx: usize,
+    raw_definition: &str,
+    dict: &DictionaryStore,
     config: &Config,
 ) -> Result<()> {
-    let Some(entry_idx) = state.selected_entry_index() else {
-        bail!("当前没有可打开的词条");
-    };
-
-    let raw_definition = definition_cache.get_or_load(dict, entry_idx)?;
     let (word, asset_dir) = dict.entry_web_context(entry_idx)?;
     let preview_file =
-        build_preview_html_file(&word, &raw_definition, &asset_dir, config.enable_javascript)?;
+        build_preview_html_file(&word, raw_definition, &asset_dir, config.enable_javascript)?;
     open_in_browser(&preview_file)?;
     Ok(())
 }
@@ -550,7 +2037,13 @@ where
 {
     enable_raw_mode().context("无法开启 raw mode")?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen).context("无法进入备用屏幕")?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableBracketedPaste,
+        EnableMouseCapture
+    )
+    .context("无法进入备用屏幕")?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend).context("无法创建终端")?;
 
@@ -566,7 +2059,13 @@ where
 
 fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
     disable_raw_mode().context("无法关闭 raw mode")?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen).context("无法退出备用屏幕")?;
+    execute!(
+        terminal.backend_mut(),
+        DisableBracketedPaste,
+        DisableMouseCapture,
+        LeaveAlternateScreen
+    )
+    .context("无法退出备用屏幕")?;
     terminal.show_cursor().context("无法恢复光标")?;
     Ok(())
 }
@@ -0,0 +1,394 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use unicode_width::UnicodeWidthChar;
+
+use crate::ui::keymap::{fixed_command, FixedKey};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditCommand {
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    LineStart,
+    LineEnd,
+    WordLeft,
+    WordRight,
+    DeleteWord,
+    DeleteToStart,
+    DeleteToEnd,
+}
+
+/// `LineEditor::handle_key` 的按键。
+pub const EDIT_KEYS: [FixedKey<EditCommand>; 11] = [
+    FixedKey {
+        keys: &["Backspace"],
+        label: "删除光标前的字符",
+        command: EditCommand::Backspace,
+    },
+    FixedKey {
+        keys: &["Delete"],
+        label: "删除光标后的字符",
+        command: EditCommand::Delete,
+    },
+    FixedKey {
+        keys: &["Left"],
+        label: "光标左移",
+        command: EditCommand::Left,
+    },
+    FixedKey {
+        keys: &["Right"],
+        label: "光标右移",
+        command: EditCommand::Right,
+    },
+    FixedKey {
+        keys: &["Ctrl+A"],
+        label: "光标移到行首",
+        command: EditCommand::LineStart,
+    },
+    FixedKey {
+        keys: &["Ctrl+E"],
+        label: "光标移到行尾",
+        command: EditCommand::LineEnd,
+    },
+    FixedKey {
+        keys: &["Alt+B", "Ctrl+Left"],
+        label: "光标按词后退",
+        command: EditCommand::WordLeft,
+    },
+    FixedKey {
+        keys: &["Alt+F", "Ctrl+Right"],
+        label: "光标按词前进",
+        command: EditCommand::WordRight,
+    },
+    FixedKey {
+        keys: &["Ctrl+W"],
+        label: "删除光标前的一个词",
+        command: EditCommand::DeleteWord,
+    },
+    FixedKey {
+        keys: &["Ctrl+U"],
+        label: "删除光标前的全部内容",
+        command: EditCommand::DeleteToStart,
+    },
+    FixedKey {
+        keys: &["Ctrl+K"],
+        label: "删除光标后的全部内容",
+        command: EditCommand::DeleteToEnd,
+    },
+];
+/// 帮助窗口中输入框除 `EDIT_KEYS` 之外的操作。
+pub const EDIT_NOTES: [(&str, &str); 2] = [
+    ("字符", "输入并实时查询"),
+    ("粘贴", "插入剪贴板内容 (换行替换为空格)"),
+];
+
+/// 单行输入框：光标为 `text` 中的字节位置，始终落在字符边界上。
+#[derive(Debug, Default, Clone)]
+pub struct LineEditor {
+    text: String,
+    cursor: usize,
+}
+
+impl LineEditor {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// 替换全部内容，光标移到末尾。
+    pub fn set_text(&mut self, text: String) {
+        self.cursor = text.len();
+        self.text = text;
+    }
+
+    /// 粘贴的内容中换行和制表符替换为空格。
+    pub fn insert_str(&mut self, text: &str) {
+        let clean: String = text
+            .trim_end_matches(['\r', '\n'])
+            .chars()
+            .map(|ch| if ch.is_control() { ' ' } else { ch })
+            .collect();
+        self.text.insert_str(self.cursor, &clean);
+        self.cursor += clean.len();
+    }
+
+    /// 处理编辑按键，返回内容是否改变；`None` 表示不是编辑按键。
+    pub fn handle_key(&mut self, key: &KeyEvent) -> Option<bool> {
+        let Some(command) = fixed_command(&EDIT_KEYS, key) else {
+            let plain = !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+            return match key.code {
+                KeyCode::Char(ch) if !ch.is_control() && plain => {
+                    insert_char(&mut self.text, &mut self.cursor, ch);
+                    Some(true)
+                }
+                _ => None,
+            };
+        };
+        let before = self.text.len();
+        self.apply(command);
+        Some(self.text.len() != before)
+    }
+
+    fn apply(&mut self, command: EditCommand) {
+        match command {
+            EditCommand::Backspace => backspace(&mut self.text, &mut self.cursor),
+            EditCommand::Delete => delete_char(&mut self.text, self.cursor),
+            EditCommand::Left => move_left(&self.text, &mut self.cursor),
+            EditCommand::Right => move_right(&self.text, &mut self.cursor),
+            EditCommand::LineStart => self.cursor = 0,
+            EditCommand::LineEnd => self.cursor = self.text.len(),
+            EditCommand::WordLeft => {
+                self.cursor = prev_word_start(&self.text, self.cursor, is_word_separator);
+            }
+            EditCommand::WordRight => self.cursor = next_word_end(&self.text, self.cursor),
+            EditCommand::DeleteWord => {
+                let start = prev_word_start(&self.text, self.cursor, char::is_whitespace);
+                self.text.replace_range(start..self.cursor, "");
+                self.cursor = start;
+            }
+            EditCommand::DeleteToStart => {
+                self.text.replace_range(..self.cursor, "");
+                self.cursor = 0;
+            }
+            EditCommand::DeleteToEnd => self.text.truncate(self.cursor),
+        }
+    }
+
+    /// 宽度为 `width` 时可见的文本和光标所在列。
+    pub fn view(&self, width: u16) -> (String, u16) {
+        single_line_view(&self.text, self.cursor, width)
+    }
+}
+
+fn is_word_separator(ch: char) -> bool {
+    !ch.is_alphanumeric()
+}
+
+/// 先跳过光标前的分隔符，再跳过一个词，返回词首位置。
+fn prev_word_start(text: &str, cursor: usize, is_separator: fn(char) -> bool) -> usize {
+    let mut chars = text[..cursor].char_indices().rev().peekable();
+    while chars.next_if(|&(_, ch)| is_separator(ch)).is_some() {}
+    let mut start = 0;
+    while let Some((idx, _)) = chars.next_if(|&(_, ch)| !is_separator(ch)) {
+        start = idx;
+    }
+    start
+}
+
+fn next_word_end(text: &str, cursor: usize) -> usize {
+    let mut chars = text[cursor..].char_indices().peekable();
+    while chars.next_if(|&(_, ch)| is_word_separator(ch)).is_some() {}
+    while chars.next_if(|&(_, ch)| !is_word_separator(ch)).is_some() {}
+    chars
+        .peek()
+        .map(|&(idx, _)| cursor + idx)
+        .unwrap_or(text.len())
+}
+
+/// 按终端显示宽度滚动，全角字符占两列；光标所在的字符 (在末尾时为一列空白) 始终可见。
+pub fn single_line_view(text: &str, cursor: usize, width: u16) -> (String, u16) {
+    let width = width as usize;
+    if width == 0 {
+        return (String::new(), 0);
+    }
+
+    let cursor_width = text[cursor..]
+        .chars()
+        .next()
+        .map_or(1, |ch| char_width(ch).max(1));
+    let mut scroll = cursor;
+    let mut cursor_x = 0;
+    for (idx, ch) in text[..cursor].char_indices().rev() {
+        if cursor_x + char_width(ch) + cursor_width > width {
+            break;
+        }
+        cursor_x += char_width(ch);
+        scroll = idx;
+    }
+
+    let mut used = 0;
+    let visible = text[scroll..]
+        .chars()
+        .take_while(|&ch| {
+            used += char_width(ch);
+            used <= width
+        })
+        .collect::<String>();
+    (visible, cursor_x.min(width - 1) as u16)
+}
+
+fn char_width(ch: char) -> usize {
+    ch.width().unwrap_or(0)
+}
+
+pub fn insert_char(text: &mut String, cursor: &mut usize, ch: char) {
+    text.insert(*cursor, ch);
+    *cursor += ch.len_utf8();
+}
+
+pub fn backspace(text: &mut String, cursor: &mut usize) {
+    if *cursor == 0 {
+        return;
+    }
+
+    let prev = text[..*cursor]
+        .char_indices()
+        .last()
+        .map(|(idx, _)| idx)
+        .unwrap_or(0);
+    text.replace_range(prev..*cursor, "");
+    *cursor = prev;
+}
+
+pub fn delete_char(text: &mut String, cursor: usize) {
+    if cursor >= text.len() {
+        return;
+    }
+
+    let next = text[cursor..]
+        .char_indices()
+        .nth(1)
+        .map(|(idx, _)| cursor + idx)
+        .unwrap_or(text.len());
+    text.replace_range(cursor..next, "");
+}
+
+pub fn move_left(text: &str, cursor: &mut usize) {
+    if *cursor == 0 {
+        return;
+    }
+    *cursor = text[..*cursor]
+        .char_indices()
+        .last()
+        .map(|(idx, _)| idx)
+        .unwrap_or(0);
+}
+
+pub fn move_right(text: &str, cursor: &mut usize) {
+    if *cursor >= text.len() {
+        return;
+    }
+    *cursor = text[*cursor..]
+        .char_indices()
+        .nth(1)
+        .map(|(idx, _)| *cursor + idx)
+        .unwrap_or(text.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(editor: &mut LineEditor, code: KeyCode, modifiers: KeyModifiers) -> Option<bool> {
+        editor.handle_key(&KeyEvent::new(code, modifiers))
+    }
+
+    fn editor_with(text: &str) -> LineEditor {
+        let mut editor = LineEditor::default();
+        editor.set_text(text.to_string());
+        editor
+    }
+
+    #[test]
+    fn typing_and_deleting_handle_multibyte_chars() {
+        let mut editor = LineEditor::default();
+        for ch in "词典a".chars() {
+            assert_eq!(
+                press(&mut editor, KeyCode::Char(ch), KeyModifiers::NONE),
+                Some(true)
+            );
+        }
+        assert_eq!(editor.text(), "词典a");
+        press(&mut editor, KeyCode::Left, KeyModifiers::NONE);
+        press(&mut editor, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(editor.text(), "词a");
+        assert_eq!(
+            press(&mut editor, KeyCode::Delete, KeyModifiers::NONE),
+            Some(true)
+        );
+        assert_eq!(editor.text(), "词");
+        assert_eq!(
+            press(&mut editor, KeyCode::Delete, KeyModifiers::NONE),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn line_start_and_end_move_cursor() {
+        let mut editor = editor_with("look up");
+        press(&mut editor, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        assert_eq!(editor.cursor, 0);
+        press(&mut editor, KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(editor.text(), "xlook up");
+        press(&mut editor, KeyCode::Char('e'), KeyModifiers::CONTROL);
+        assert_eq!(editor.cursor, editor.text().len());
+    }
+
+    #[test]
+    fn word_moves_skip_separators() {
+        let mut editor = editor_with("take-off now");
+        press(&mut editor, KeyCode::Char('b'), KeyModifiers::ALT);
+        assert_eq!(editor.cursor, 9);
+        press(&mut editor, KeyCode::Char('b'), KeyModifiers::ALT);
+        assert_eq!(editor.cursor, 5);
+        press(&mut editor, KeyCode::Left, KeyModifiers::CONTROL);
+        assert_eq!(editor.cursor, 0);
+        press(&mut editor, KeyCode::Char('f'), KeyModifiers::ALT);
+        assert_eq!(editor.cursor, 4);
+        press(&mut editor, KeyCode::Right, KeyModifiers::CONTROL);
+        assert_eq!(editor.cursor, 8);
+    }
+
+    #[test]
+    fn kill_commands_remove_text() {
+        let mut editor = editor_with("take-off now");
+        press(&mut editor, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(editor.text(), "take-off ");
+        press(&mut editor, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(editor.text(), "");
+
+        let mut editor = editor_with("take-off now");
+        editor.cursor = 4;
+        press(&mut editor, KeyCode::Char('k'), KeyModifiers::CONTROL);
+        assert_eq!(editor.text(), "take");
+        editor.cursor = 2;
+        press(&mut editor, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(editor.text(), "ke");
+        assert_eq!(editor.cursor, 0);
+    }
+
+    #[test]
+    fn unbound_control_keys_are_not_edits() {
+        let mut editor = editor_with("run");
+        assert_eq!(
+            press(&mut editor, KeyCode::Char('z'), KeyModifiers::CONTROL),
+            None
+        );
+        assert_eq!(press(&mut editor, KeyCode::Enter, KeyModifiers::NONE), None);
+        assert_eq!(editor.text(), "run");
+    }
+
+    #[test]
+    fn paste_replaces_control_chars() {
+        let mut editor = editor_with("a");
+        editor.insert_str("b\tc\nd\r\n");
+        assert_eq!(editor.text(), "ab c d");
+        assert_eq!(editor.cursor, editor.text().len());
+    }
+
+    #[test]
+    fn view_scrolls_to_keep_cursor_visible() {
+        assert_eq!(single_line_view("abcdef", 6, 4), ("def".to_string(), 3));
+        assert_eq!(single_line_view("abcdef", 0, 4), ("abcd".to_string(), 0));
+        assert_eq!(single_line_view("词典", 3, 0), (String::new(), 0));
+    }
+
+    #[test]
+    fn view_counts_full_width_chars_as_two_columns() {
+        assert_eq!(single_line_view("词典查询", 12, 5), ("查询".to_string(), 4));
+        assert_eq!(single_line_view("词典查询", 3, 4), ("词典".to_string(), 2));
+        assert_eq!(single_line_view("a词b", 1, 2), ("词".to_string(), 0));
+        assert_eq!(single_line_view("run 跑", 4, 10), ("run 跑".to_string(), 4));
+    }
+}
@@ -0,0 +1,247 @@
+use std::str::FromStr;
+
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::app::Config;
+
+pub const THEME_NAMES: [&str; 3] = ["dark", "light", "mono"];
+pub const DEFAULT_THEME: &str = "dark";
+
+/// 界面各部分使用的样式。配置中的 `theme_overrides` 按槽位名覆盖。
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub border: Style,
+    /// 选中的字段、弹出窗口
+    pub border_focus: Style,
+    /// 正在编辑的字段、查找输入框
+    pub border_editing: Style,
+    /// 列表中的选中行
+    pub highlight: Style,
+    pub headword: Style,
+    pub phonetic: Style,
+    pub example: Style,
+    pub link: Style,
+    /// 列表符号、词性、代码等需要与正文区分的文字
+    pub accent: Style,
+    /// 详情栏查找的匹配项和当前匹配项
+    pub find_match: Style,
+    pub find_current: Style,
+    pub status: Style,
+    pub error: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// 可在 `theme_overrides` 中使用的槽位名。
+    pub const SLOTS: [&'static str; 13] = [
+        "border",
+        "border-focus",
+        "border-editing",
+        "highlight",
+        "headword",
+        "phonetic",
+        "example",
+        "link",
+        "accent",
+        "find-match",
+        "find-current",
+        "status",
+        "error",
+    ];
+
+    pub fn named(name: &str) -> Option<Theme> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "mono" => Some(Self::dark().without_colors()),
+            _ => None,
+        }
+    }
+
+    /// 按配置生成主题，返回无法识别的主题名、槽位和样式。
+    /// 设置了 `NO_COLOR` 环境变量时去掉所有颜色，只保留粗体、下划线等修饰。
+    pub fn from_config(config: &Config) -> (Theme, Vec<String>) {
+        let mut warnings = Vec::new();
+        let mut theme = Self::named(&config.theme).unwrap_or_else(|| {
+            warnings.push(format!(
+                "未知主题: {} (可选 {})",
+                config.theme,
+                THEME_NAMES.join("/")
+            ));
+            Self::dark()
+        });
+        for (slot, spec) in &config.theme_overrides {
+            let Some(target) = theme.slot_mut(slot) else {
+                warnings.push(format!(
+                    "未知主题槽位: {slot} (可选 {})",
+                    Self::SLOTS.join("/")
+                ));
+                continue;
+            };
+            match parse_style(spec) {
+                Ok(style) => *target = style,
+                Err(err) => warnings.push(format!("{slot}: {err}")),
+            }
+        }
+        if no_color_requested() {
+            theme = theme.without_colors();
+        }
+        (theme, warnings)
+    }
+
+    /// 状态文字中含有失败、错误、无效时使用 `error` 样式。
+    pub fn status_style(&self, status: &str) -> Style {
+        if ["失败", "错误", "无效"]
+            .iter()
+            .any(|word| status.contains(word))
+        {
+            self.error
+        } else {
+            self.status
+        }
+    }
+
+    fn dark() -> Theme {
+        Theme {
+            border: Style::default(),
+            border_focus: Style::default().fg(Color::Cyan),
+            border_editing: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            highlight: Style::default().add_modifier(Modifier::REVERSED),
+            headword: Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+            phonetic: Style::default().fg(Color::LightGreen),
+            example: Style::default()
+                .fg(Color::Gray)
+                .add_modifier(Modifier::ITALIC),
+            link: Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::UNDERLINED),
+            accent: Style::default().fg(Color::Yellow),
+            find_match: Style::default().fg(Color::Black).bg(Color::Yellow),
+            find_current: Style::default().fg(Color::Black).bg(Color::LightRed),
+            status: Style::default().fg(Color::Green),
+            error: Style::default()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+        }
+    }
+
+    fn light() -> Theme {
+        Theme {
+            border: Style::default().fg(Color::DarkGray),
+            border_focus: Style::default().fg(Color::Blue),
+            border_editing: Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+            highlight: Style::default().fg(Color::White).bg(Color::Blue),
+            headword: Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+            phonetic: Style::default().fg(Color::Green),
+            example: Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+            link: Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::UNDERLINED),
+            accent: Style::default().fg(Color::Magenta),
+            find_match: Style::default().fg(Color::Black).bg(Color::LightYellow),
+            find_current: Style::default().fg(Color::White).bg(Color::Red),
+            status: Style::default().fg(Color::DarkGray),
+            error: Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+        }
+    }
+
+    /// 去掉前景色和背景色；选中行和查找匹配改用反色，聚焦和编辑中的边框、当前匹配改用粗体，保证仍可区分。
+    fn without_colors(self) -> Theme {
+        let strip = |style: Style| Style {
+            fg: None,
+            bg: None,
+            underline_color: None,
+            ..style
+        };
+        Theme {
+            border: strip(self.border),
+            border_focus: strip(self.border_focus).add_modifier(Modifier::BOLD),
+            border_editing: strip(self.border_editing).add_modifier(Modifier::BOLD),
+            highlight: strip(self.highlight).add_modifier(Modifier::REVERSED),
+            headword: strip(self.headword),
+            phonetic: strip(self.phonetic),
+            example: strip(self.example),
+            link: strip(self.link),
+            accent: strip(self.accent),
+            find_match: strip(self.find_match).add_modifier(Modifier::REVERSED),
+            find_current: strip(self.find_current)
+                .add_modifier(Modifier::REVERSED | Modifier::BOLD | Modifier::UNDERLINED),
+            status: strip(self.status),
+            error: strip(self.error),
+        }
+    }
+
+    fn slot_mut(&mut self, slot: &str) -> Option<&mut Style> {
+        let style = match slot.trim().to_ascii_lowercase().as_str() {
+            "border" => &mut self.border,
+            "border-focus" => &mut self.border_focus,
+            "border-editing" => &mut self.border_editing,
+            "highlight" => &mut self.highlight,
+            "headword" => &mut self.headword,
+            "phonetic" => &mut self.phonetic,
+            "example" => &mut self.example,
+            "link" => &mut self.link,
+            "accent" => &mut self.accent,
+            "find-match" => &mut self.find_match,
+            "find-current" => &mut self.find_current,
+            "status" => &mut self.status,
+            "error" => &mut self.error,
+            _ => return None,
+        };
+        Some(style)
+    }
+}
+
+fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// 解析 `bold yellow on blue` 形式的样式：颜色名、`#rrggbb` 或 0-255 为前景色，
+/// `on` 之后为背景色，另可加 bold/dim/italic/underline/reversed。
+fn parse_style(spec: &str) -> Result<Style, String> {
+    let mut style = Style::default();
+    let mut tokens = spec.split_whitespace();
+    while let Some(token) = tokens.next() {
+        let token = token.to_ascii_lowercase();
+        let modifier = match token.as_str() {
+            "bold" => Modifier::BOLD,
+            "dim" => Modifier::DIM,
+            "italic" => Modifier::ITALIC,
+            "underline" | "underlined" => Modifier::UNDERLINED,
+            "reversed" | "reverse" => Modifier::REVERSED,
+            "default" | "none" => continue,
+            "on" => {
+                let color = tokens.next().ok_or("on 后面缺少背景色")?;
+                style = style.bg(parse_color(color)?);
+                continue;
+            }
+            _ => {
+                style = style.fg(parse_color(&token)?);
+                continue;
+            }
+        };
+        style = style.add_modifier(modifier);
+    }
+    Ok(style)
+}
+
+fn parse_color(name: &str) -> Result<Color, String> {
+    Color::from_str(name).map_err(|_| format!("无法识别的颜色: {name}"))
+}